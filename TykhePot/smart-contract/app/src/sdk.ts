@@ -1,6 +1,7 @@
 import { Connection, PublicKey, Transaction, SystemProgram, SYSVAR_CLOCK_PUBKEY } from '@solana/web3.js';
 import { Program, AnchorProvider, web3, BN, Idl } from '@coral-xyz/anchor';
 import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from '@solana/spl-token';
+import { sha256 } from '@noble/hashes/sha256';
 
 // 程序ID
 export const ROYALPOT_PROGRAM_ID = new PublicKey('RoyaltPot1111111111111111111111111111111111');
@@ -108,6 +109,50 @@ export class RoyalPotSDK {
     };
   }
 
+  /**
+   * 复现开奖结果 (与链上 randomness::pick_winners 完全一致)
+   * drawSeed = DrawExecuted.draw_seed, total = DrawExecuted.participant_count
+   * 返回 11 个参与者下标: [1st, 2nd×2, 3rd×3, lucky×5]
+   * 例: pickWinners(new Uint8Array(32).fill(7), 100)
+   *   = [92, 77, 28, 71, 22, 36, 97, 63, 99, 21, 29] (randomness.rs 测试中固定)
+   */
+  static pickWinners(drawSeed: Uint8Array, total: number): number[] {
+    const WINNER_COUNT = 11;
+    if (total < WINNER_COUNT) throw new Error('need at least 11 participants');
+
+    const U64_MAX = (BigInt(1) << BigInt(64)) - BigInt(1);
+    let counter = BigInt(0);
+    // sha256(seed || counter as u64 LE)，浏览器和 Node 通用
+    const msg = new Uint8Array(40);
+    msg.set(drawSeed, 0);
+    const nextU64 = (): bigint => {
+      new DataView(msg.buffer).setBigUint64(32, counter, true);
+      counter += BigInt(1);
+      const h = sha256(msg);
+      return new DataView(h.buffer, h.byteOffset, 8).getBigUint64(0, true);
+    };
+    const below = (bound: bigint): bigint => {
+      const zone = U64_MAX - (U64_MAX % bound);
+      for (;;) {
+        const x = nextU64();
+        if (x < zone) return x % bound;
+      }
+    };
+
+    const moved = new Map<number, number>();
+    const valueAt = (pos: number) => moved.get(pos) ?? pos;
+    const winners: number[] = [];
+    for (let i = 0; i < WINNER_COUNT; i++) {
+      const j = i + Number(below(BigInt(total - i)));
+      const vi = valueAt(i);
+      const vj = valueAt(j);
+      moved.set(j, vi);
+      moved.set(i, vj);
+      winners.push(vj);
+    }
+    return winners;
+  }
+
   /**
    * 获取PDA地址
    */
@@ -0,0 +1,103 @@
+//! Deterministic stand-in for the Switchboard On-Demand randomness oracle.
+//!
+//! LOCAL TEST VALIDATORS ONLY. royalpot accepts accounts owned by this program
+//! only when built with `--features mock-oracle`.
+//!
+//! Same commit → reveal flow as Switchboard:
+//!   1. `commit` in slot N-1 fixes seed_slot and clears the previous value
+//!   2. royalpot `request_vrf` in slot N binds the round to seed_slot
+//!   3. `reveal` + royalpot `execute_draw_vrf` in one transaction (slot > seed_slot)
+//!
+//! value = sha256("tykhepot-mock-oracle" || randomness_account || seed_slot),
+//! so a test can compute the winners before it draws.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+declare_id!("H8cBoQsrXbWgSAyL19EoPvFPNJZ5XLqhdbtfZ8CM7sxU");
+
+pub const MOCK_VALUE_DOMAIN: &[u8] = b"tykhepot-mock-oracle";
+
+/// Value the oracle will reveal for `randomness` committed at `seed_slot`.
+pub fn mock_value(randomness: &Pubkey, seed_slot: u64) -> [u8; 32] {
+    hashv(&[
+        MOCK_VALUE_DOMAIN,
+        randomness.as_ref(),
+        &seed_slot.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+#[program]
+pub mod mock_oracle {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let r = &mut ctx.accounts.randomness;
+        r.authority = ctx.accounts.authority.key();
+        r.seed_slot = 0;
+        r.reveal_slot = 0;
+        r.value = [0u8; 32];
+        Ok(())
+    }
+
+    pub fn commit(ctx: Context<Update>) -> Result<()> {
+        let clock = Clock::get()?;
+        let r = &mut ctx.accounts.randomness;
+        r.seed_slot = clock.slot;
+        r.reveal_slot = 0;
+        r.value = [0u8; 32];
+        Ok(())
+    }
+
+    pub fn reveal(ctx: Context<Update>) -> Result<()> {
+        let clock = Clock::get()?;
+        let key = ctx.accounts.randomness.key();
+        let r = &mut ctx.accounts.randomness;
+        require!(r.seed_slot != 0, MockOracleError::NotCommitted);
+        require!(clock.slot > r.seed_slot, MockOracleError::RevealTooEarly);
+        r.value = mock_value(&key, r.seed_slot);
+        r.reveal_slot = clock.slot;
+        Ok(())
+    }
+}
+
+/// Field order mirrors what royalpot's randomness::read_mock_randomness expects.
+#[account]
+pub struct MockRandomness {
+    pub authority: Pubkey,
+    pub seed_slot: u64,
+    pub reveal_slot: u64,
+    pub value: [u8; 32],
+}
+
+impl MockRandomness {
+    pub const SIZE: usize = 32 + 8 + 8 + 32;
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(init, payer = authority, space = 8 + MockRandomness::SIZE)]
+    pub randomness: Account<'info, MockRandomness>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Update<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, has_one = authority)]
+    pub randomness: Account<'info, MockRandomness>,
+}
+
+#[error_code]
+pub enum MockOracleError {
+    #[msg("Randomness has not been committed")]
+    NotCommitted,
+    #[msg("Reveal must happen in a later slot than the commit")]
+    RevealTooEarly,
+}
@@ -1,26 +1,69 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Transfer};
 
+pub mod merkle;
+
 // 空投模块常量
 pub const AIRDROP_CLAIM_MULTIPLIER: u64 = 10; // 获利金额的10倍
 pub const MAX_AIRDROP_PER_USER: u64 = 10_000_000_000_000; // 10,000 TPOT
 pub const MIN_PROFIT_TO_CLAIM: u64 = 1_000_000_000; // 1,000 TPOT
+pub const AIRDROP_STATE_VERSION: u8 = 2;
+// 每个领取位图分片覆盖的叶子数
+pub const DISTRIBUTION_CHUNK_BYTES: usize = 256;
+pub const DISTRIBUTION_CHUNK_BITS: u32 = (DISTRIBUTION_CHUNK_BYTES * 8) as u32;
 
 // 数据结构
 #[account]
 pub struct AirdropState {
+    pub version: u8,
     pub authority: Pubkey,
     pub token_mint: Pubkey,
     pub total_airdrop: u64,
     pub claimed_amount: u64,
+    // 尚未分配给任何赛季的额度
     pub remaining_amount: u64,
     pub participant_count: u32,
     pub claimed_count: u32,
     pub bump: u8,
+    // 当前赛季（0 = 尚未开始任何赛季）
+    pub season: u32,
+    pub season_budget: u64,
+    pub season_claimed: u64,
+    pub season_start: i64,
+    pub season_end: i64,
+    // 本赛季剩余额度已被 sweep_unclaimed 取走
+    pub season_swept: bool,
+    // 盈利空投 / 赛季 / Merkle 分发共用的金库（VAULT_KIND_PROFIT_AIRDROP，由 global_state 持有）
+    pub vault: Pubkey,
 }
 
 impl AirdropState {
-    pub const SIZE: usize = 32 + 32 + 8 + 8 + 8 + 4 + 4 + 1;
+    pub const SIZE: usize = 1 + 32 + 32 + 8 + 8 + 8 + 4 + 4 + 1 + 4 + 8 + 8 + 8 + 8 + 1 + 32;
+
+    // 赛季在 [season_start, season_end) 内且未被清扫时可领取
+    pub fn season_open(&self, now: i64) -> bool {
+        self.season > 0 && !self.season_swept && now >= self.season_start && now < self.season_end
+    }
+
+    pub fn season_unclaimed(&self) -> u64 {
+        self.season_budget.saturating_sub(self.season_claimed)
+    }
+
+    // 已分配（赛季预算 / Merkle 分发）但尚未领取的额度，金库至少要持有这么多
+    pub fn committed(&self) -> u64 {
+        self.total_airdrop
+            .saturating_sub(self.claimed_amount)
+            .saturating_sub(self.remaining_amount)
+    }
+}
+
+// 新的分配生效后，金库余额须覆盖全部已分配未领取的额度
+fn require_vault_covers(airdrop_state: &AirdropState, vault_balance: u64) -> Result<()> {
+    require!(
+        vault_balance >= airdrop_state.committed(),
+        AirdropErrorCode::VaultUnderfunded
+    );
+    Ok(())
 }
 
 #[account]
@@ -37,16 +80,85 @@ pub struct UserAirdrop {
 
 impl UserAirdrop {
     pub const SIZE: usize = 32 + 1 + 8 + 8 + 8 + 1 + 8 + 8;
+
+    // 按当前净盈利重算总盈利和可领取额度，返回新增的盈利。
+    // 净盈利回落到 0 或以下时，可领取额度随之降为 0（已领取的部分不追回）
+    pub fn sync_profit(&mut self, net_pnl: i64) -> Result<u64> {
+        let net_profit = net_pnl.max(0) as u64;
+        let profit_amount = net_profit.saturating_sub(self.total_profit);
+        self.total_profit = net_profit;
+
+        // 计算可领取空投（10倍盈利，最高10000 TPOT），扣除历次已领取的部分
+        let calculated_airdrop = net_profit
+            .checked_mul(AIRDROP_CLAIM_MULTIPLIER)
+            .ok_or(AirdropErrorCode::MathOverflow)?
+            .min(MAX_AIRDROP_PER_USER);
+        self.eligible_airdrop = calculated_airdrop.saturating_sub(self.claimed_amount);
+        Ok(profit_amount)
+    }
+}
+
+// Merkle 分发：叶子为 merkle::leaf(index, claimant, amount)，链下计算名单
+#[account]
+pub struct Distribution {
+    pub root: [u8; 32],
+    pub total: u64,
+    pub claimed_amount: u64,
+    pub claimed_count: u32,
+    pub created_at: i64,
+    // 过期后不可再领取，close_distribution 把未领取部分退回未分配额度
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl Distribution {
+    pub const SIZE: usize = 32 + 8 + 8 + 4 + 8 + 8 + 1;
+}
+
+// 领取位图分片：覆盖叶子 [chunk * DISTRIBUTION_CHUNK_BITS, (chunk + 1) * DISTRIBUTION_CHUNK_BITS)
+// 由首个落在该分片的领取者创建
+#[account]
+pub struct DistributionClaims {
+    pub distribution: Pubkey,
+    pub chunk: u32,
+    pub bits: [u8; DISTRIBUTION_CHUNK_BYTES],
+    pub bump: u8,
 }
 
-// 初始化空投模块
+impl DistributionClaims {
+    pub const SIZE: usize = 32 + 4 + DISTRIBUTION_CHUNK_BYTES + 1;
+
+    pub fn is_claimed(&self, index: u32) -> bool {
+        let bit = index % DISTRIBUTION_CHUNK_BITS;
+        self.bits[(bit / 8) as usize] & (1 << (bit % 8)) != 0
+    }
+
+    pub fn set_claimed(&mut self, index: u32) {
+        let bit = index % DISTRIBUTION_CHUNK_BITS;
+        self.bits[(bit / 8) as usize] |= 1 << (bit % 8);
+    }
+}
+
+// 初始化空投模块：仅 global_state.authority（或其理事会成员）可调用，
+// AirdropState.authority 随之设为 global_state.authority
 pub fn initialize_airdrop(
     ctx: Context<crate::InitializeAirdrop>,
     total_airdrop: u64,
 ) -> Result<()> {
+    let authority = ctx.accounts.global_state.authority;
+    require!(
+        crate::is_authority(
+            &authority,
+            &ctx.accounts.payer.key(),
+            ctx.accounts.admin_council.as_deref(),
+        ),
+        crate::ErrorCode::Unauthorized
+    );
+
     let airdrop_state = &mut ctx.accounts.airdrop_state;
 
-    airdrop_state.authority = ctx.accounts.payer.key();
+    airdrop_state.version = AIRDROP_STATE_VERSION;
+    airdrop_state.authority = authority;
     airdrop_state.token_mint = ctx.accounts.token_mint.key();
     airdrop_state.total_airdrop = total_airdrop;
     airdrop_state.claimed_amount = 0;
@@ -54,15 +166,35 @@ pub fn initialize_airdrop(
     airdrop_state.participant_count = 0;
     airdrop_state.claimed_count = 0;
     airdrop_state.bump = ctx.bumps.airdrop_state;
+    airdrop_state.season = 0;
+    airdrop_state.season_budget = 0;
+    airdrop_state.season_claimed = 0;
+    airdrop_state.season_start = 0;
+    airdrop_state.season_end = 0;
+    airdrop_state.season_swept = false;
+    airdrop_state.vault = ctx.accounts.airdrop_vault.key();
 
     emit!(AirdropInitialized { total_airdrop });
 
     Ok(())
 }
 
-// 记录用户游戏盈利
-pub fn record_profit(ctx: Context<crate::RecordProfit>, profit_amount: u64) -> Result<()> {
-    require!(profit_amount > 0, AirdropErrorCode::InvalidProfit);
+// 同步用户游戏盈利：取自链上 PlayerStats.net_pnl（存款、中奖、退款时累计），
+// 调用者无法自报金额，因此任何人都可代为调用
+pub fn record_profit(ctx: Context<crate::RecordProfit>) -> Result<()> {
+    // 检查暂停状态
+    require!(
+        !ctx.accounts.global_state.paused(crate::PAUSE_AIRDROP),
+        crate::ErrorCode::ProtocolPaused
+    );
+
+    // 只计入净盈利；从未盈利过的钱包没有可记录的盈利，
+    // 已登记的钱包则始终可以重新同步（包括回落到净亏损）
+    let net_pnl = ctx.accounts.player_stats.net_pnl;
+    require!(
+        net_pnl > 0 || ctx.accounts.user_airdrop.has_participated,
+        AirdropErrorCode::InvalidProfit
+    );
 
     let airdrop_state = &mut ctx.accounts.airdrop_state;
     let user_airdrop = &mut ctx.accounts.user_airdrop;
@@ -76,25 +208,8 @@ pub fn record_profit(ctx: Context<crate::RecordProfit>, profit_amount: u64) -> R
         airdrop_state.participant_count += 1;
     }
 
-    // 更新总盈利（saturating_add 防止溢出 abort）
-    user_airdrop.total_profit = user_airdrop.total_profit.saturating_add(profit_amount);
-
-    // 计算可领取空投（10倍盈利，最高10000 TPOT）
-    let calculated_airdrop = user_airdrop
-        .total_profit
-        .checked_mul(AIRDROP_CLAIM_MULTIPLIER)
-        .ok_or(AirdropErrorCode::MathOverflow)?
-        .min(MAX_AIRDROP_PER_USER);
-
-    // 已经领取的部分
-    let already_claimed = if user_airdrop.has_claimed {
-        user_airdrop.claimed_amount
-    } else {
-        0
-    };
-
-    // 更新可领取额度
-    user_airdrop.eligible_airdrop = calculated_airdrop.saturating_sub(already_claimed);
+    // 总盈利跟随当前净盈利（之后再亏损也会相应下调，最低为 0）
+    let profit_amount = user_airdrop.sync_profit(net_pnl)?;
 
     emit!(ProfitRecorded {
         user: ctx.accounts.user.key(),
@@ -106,8 +221,242 @@ pub fn record_profit(ctx: Context<crate::RecordProfit>, profit_amount: u64) -> R
     Ok(())
 }
 
-// 领取基于盈利的空投
+// 开启新赛季：从未分配额度中划出预算。上一赛季须已结束并清扫
+pub fn start_airdrop_season(
+    ctx: Context<crate::StartAirdropSeason>,
+    budget: u64,
+    start: i64,
+    end: i64,
+) -> Result<()> {
+    let airdrop_state = &mut ctx.accounts.airdrop_state;
+    require!(
+        airdrop_state.season == 0 || airdrop_state.season_swept,
+        AirdropErrorCode::SeasonNotSwept
+    );
+    require!(budget > 0 && start < end, AirdropErrorCode::InvalidSeason);
+    require!(
+        budget <= airdrop_state.remaining_amount,
+        AirdropErrorCode::AirdropExhausted
+    );
+
+    airdrop_state.remaining_amount -= budget;
+    airdrop_state.season = airdrop_state
+        .season
+        .checked_add(1)
+        .ok_or(AirdropErrorCode::MathOverflow)?;
+    airdrop_state.season_budget = budget;
+    airdrop_state.season_claimed = 0;
+    airdrop_state.season_start = start;
+    airdrop_state.season_end = end;
+    airdrop_state.season_swept = false;
+    require_vault_covers(airdrop_state, ctx.accounts.airdrop_vault.amount)?;
+
+    emit!(AirdropSeasonStarted {
+        season: airdrop_state.season,
+        budget,
+        start,
+        end,
+    });
+
+    Ok(())
+}
+
+// 赛季结束后，把未领取的预算从空投金库转到 destination
+pub fn sweep_unclaimed(ctx: Context<crate::SweepUnclaimed>) -> Result<()> {
+    let airdrop_state = &mut ctx.accounts.airdrop_state;
+    let clock = Clock::get()?;
+    require!(airdrop_state.season > 0, AirdropErrorCode::InvalidSeason);
+    require!(
+        !airdrop_state.season_swept,
+        AirdropErrorCode::SeasonAlreadySwept
+    );
+    require!(
+        clock.unix_timestamp >= airdrop_state.season_end,
+        AirdropErrorCode::SeasonNotEnded
+    );
+
+    let amount = airdrop_state.season_unclaimed();
+    if amount > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.airdrop_vault.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: ctx.accounts.global_state.to_account_info(),
+                },
+                &[&[b"global_state", &[ctx.accounts.global_state.bump]]],
+            ),
+            amount,
+        )?;
+    }
+    // 清扫走的代币不再属于空投总额
+    airdrop_state.total_airdrop = airdrop_state.total_airdrop.saturating_sub(amount);
+    airdrop_state.season_swept = true;
+
+    emit!(AirdropSeasonSwept {
+        season: airdrop_state.season,
+        amount,
+        destination: ctx.accounts.destination.key(),
+    });
+
+    Ok(())
+}
+
+// 创建 Merkle 分发：从未分配额度中划出 total，expires_at 之前可领取
+pub fn create_distribution(
+    ctx: Context<crate::CreateDistribution>,
+    root: [u8; 32],
+    total: u64,
+    expires_at: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        total > 0 && expires_at > clock.unix_timestamp,
+        AirdropErrorCode::InvalidDistribution
+    );
+    let airdrop_state = &mut ctx.accounts.airdrop_state;
+    require!(
+        total <= airdrop_state.remaining_amount,
+        AirdropErrorCode::AirdropExhausted
+    );
+    airdrop_state.remaining_amount -= total;
+    require_vault_covers(airdrop_state, ctx.accounts.airdrop_vault.amount)?;
+
+    let distribution = &mut ctx.accounts.distribution;
+    distribution.root = root;
+    distribution.total = total;
+    distribution.claimed_amount = 0;
+    distribution.claimed_count = 0;
+    distribution.created_at = clock.unix_timestamp;
+    distribution.expires_at = expires_at;
+    distribution.bump = ctx.bumps.distribution;
+
+    emit!(DistributionCreated {
+        distribution: distribution.key(),
+        root,
+        total,
+        expires_at,
+    });
+
+    Ok(())
+}
+
+// 分发过期后关闭：未领取部分退回 remaining_amount（代币仍在金库），租金退给 authority。
+// 已创建的领取位图保留，同一 root 重新创建分发时不会被重复领取
+pub fn close_distribution(ctx: Context<crate::CloseDistribution>) -> Result<()> {
+    let clock = Clock::get()?;
+    let distribution = &ctx.accounts.distribution;
+    require!(
+        clock.unix_timestamp >= distribution.expires_at,
+        AirdropErrorCode::DistributionNotExpired
+    );
+
+    let returned = distribution
+        .total
+        .saturating_sub(distribution.claimed_amount);
+    let airdrop_state = &mut ctx.accounts.airdrop_state;
+    airdrop_state.remaining_amount = airdrop_state
+        .remaining_amount
+        .checked_add(returned)
+        .ok_or(AirdropErrorCode::MathOverflow)?;
+
+    emit!(DistributionClosed {
+        distribution: distribution.key(),
+        claimed_amount: distribution.claimed_amount,
+        returned,
+    });
+
+    Ok(())
+}
+
+// 凭 Merkle 证明领取分发中第 index 个叶子
+pub fn claim_distribution(
+    ctx: Context<crate::ClaimDistribution>,
+    index: u32,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    // 检查暂停状态
+    require!(
+        !ctx.accounts.global_state.paused(crate::PAUSE_AIRDROP),
+        crate::ErrorCode::ProtocolPaused
+    );
+
+    let distribution = &mut ctx.accounts.distribution;
+    let claimant = ctx.accounts.claimant.key();
+    require!(
+        Clock::get()?.unix_timestamp < distribution.expires_at,
+        AirdropErrorCode::DistributionExpired
+    );
+    require!(
+        merkle::verify(
+            &proof,
+            &distribution.root,
+            merkle::leaf(index, &claimant, amount)
+        ),
+        AirdropErrorCode::InvalidProof
+    );
+
+    let claims = &mut ctx.accounts.distribution_claims;
+    claims.distribution = distribution.key();
+    claims.chunk = index / DISTRIBUTION_CHUNK_BITS;
+    claims.bump = ctx.bumps.distribution_claims;
+    require!(
+        !claims.is_claimed(index),
+        AirdropErrorCode::DistributionAlreadyClaimed
+    );
+
+    // 名单总额超过 total 时，先到先得
+    let claimed_amount = distribution
+        .claimed_amount
+        .checked_add(amount)
+        .ok_or(AirdropErrorCode::MathOverflow)?;
+    require!(
+        claimed_amount <= distribution.total,
+        AirdropErrorCode::AirdropExhausted
+    );
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.airdrop_vault.to_account_info(),
+                to: ctx.accounts.claimant_token.to_account_info(),
+                authority: ctx.accounts.global_state.to_account_info(),
+            },
+            &[&[b"global_state", &[ctx.accounts.global_state.bump]]],
+        ),
+        amount,
+    )?;
+
+    claims.set_claimed(index);
+    distribution.claimed_amount = claimed_amount;
+    distribution.claimed_count += 1;
+    let airdrop_state = &mut ctx.accounts.airdrop_state;
+    airdrop_state.claimed_amount = airdrop_state
+        .claimed_amount
+        .checked_add(amount)
+        .ok_or(AirdropErrorCode::MathOverflow)?;
+
+    emit!(DistributionClaimed {
+        distribution: distribution.key(),
+        index,
+        claimant,
+        amount,
+    });
+
+    Ok(())
+}
+
+// 领取基于盈利的空投；可多次领取，每次领取自上次以来新增的额度
 pub fn claim_profit_airdrop(ctx: Context<crate::ClaimProfitAirdrop>) -> Result<()> {
+    // 检查暂停状态
+    require!(
+        !ctx.accounts.global_state.paused(crate::PAUSE_AIRDROP),
+        crate::ErrorCode::ProtocolPaused
+    );
+
     let airdrop_state = &mut ctx.accounts.airdrop_state;
     let user_airdrop = &mut ctx.accounts.user_airdrop;
     let clock = Clock::get()?;
@@ -118,10 +467,10 @@ pub fn claim_profit_airdrop(ctx: Context<crate::ClaimProfitAirdrop>) -> Result<(
         AirdropErrorCode::NotParticipated
     );
 
-    // 检查是否已领取过
+    // 检查赛季是否开放
     require!(
-        !user_airdrop.has_claimed,
-        AirdropErrorCode::AlreadyClaimedAirdrop
+        airdrop_state.season_open(clock.unix_timestamp),
+        AirdropErrorCode::SeasonNotActive
     );
 
     // 检查盈利是否达标
@@ -136,10 +485,10 @@ pub fn claim_profit_airdrop(ctx: Context<crate::ClaimProfitAirdrop>) -> Result<(
         AirdropErrorCode::NoEligibleAirdrop
     );
 
-    // 检查空投池余额
+    // 检查本赛季剩余预算
     let claim_amount = user_airdrop
         .eligible_airdrop
-        .min(airdrop_state.remaining_amount);
+        .min(airdrop_state.season_unclaimed());
     require!(claim_amount > 0, AirdropErrorCode::AirdropExhausted);
 
     // 转账给用户
@@ -149,26 +498,31 @@ pub fn claim_profit_airdrop(ctx: Context<crate::ClaimProfitAirdrop>) -> Result<(
             Transfer {
                 from: ctx.accounts.airdrop_vault.to_account_info(),
                 to: ctx.accounts.user_token.to_account_info(),
-                authority: ctx.accounts.airdrop_authority.to_account_info(),
+                authority: ctx.accounts.global_state.to_account_info(),
             },
-            &[&[b"airdrop", &[ctx.bumps.airdrop_authority]]],
+            &[&[b"global_state", &[ctx.accounts.global_state.bump]]],
         ),
         claim_amount,
     )?;
 
-    // 更新状态
-    user_airdrop.has_claimed = true;
-    user_airdrop.claimed_amount = claim_amount;
+    // 更新状态；claimed_count 只统计首次领取的用户
+    if !user_airdrop.has_claimed {
+        user_airdrop.has_claimed = true;
+        airdrop_state.claimed_count += 1;
+    }
+    user_airdrop.claimed_amount += claim_amount;
+    user_airdrop.eligible_airdrop -= claim_amount;
     user_airdrop.claim_time = clock.unix_timestamp;
 
     airdrop_state.claimed_amount += claim_amount;
-    airdrop_state.remaining_amount -= claim_amount;
-    airdrop_state.claimed_count += 1;
+    airdrop_state.season_claimed += claim_amount;
 
     emit!(AirdropClaimed {
         user: ctx.accounts.user.key(),
         amount: claim_amount,
         profit_based_on: user_airdrop.total_profit,
+        season: airdrop_state.season,
+        total_claimed: user_airdrop.claimed_amount,
     });
 
     Ok(())
@@ -195,6 +549,48 @@ pub struct AirdropClaimed {
     pub user: Pubkey,
     pub amount: u64,
     pub profit_based_on: u64,
+    pub season: u32,
+    // 该用户累计领取
+    pub total_claimed: u64,
+}
+
+#[event]
+pub struct DistributionCreated {
+    pub distribution: Pubkey,
+    pub root: [u8; 32],
+    pub total: u64,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct DistributionClosed {
+    pub distribution: Pubkey,
+    pub claimed_amount: u64,
+    // 退回未分配额度的部分
+    pub returned: u64,
+}
+
+#[event]
+pub struct DistributionClaimed {
+    pub distribution: Pubkey,
+    pub index: u32,
+    pub claimant: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct AirdropSeasonStarted {
+    pub season: u32,
+    pub budget: u64,
+    pub start: i64,
+    pub end: i64,
+}
+
+#[event]
+pub struct AirdropSeasonSwept {
+    pub season: u32,
+    pub amount: u64,
+    pub destination: Pubkey,
 }
 
 // 错误码
@@ -214,4 +610,71 @@ pub enum AirdropErrorCode {
     AirdropExhausted,
     #[msg("Arithmetic overflow")]
     MathOverflow,
+    #[msg("No airdrop season is open")]
+    SeasonNotActive,
+    #[msg("Invalid airdrop season")]
+    InvalidSeason,
+    #[msg("Airdrop season has not ended")]
+    SeasonNotEnded,
+    #[msg("Airdrop season already swept")]
+    SeasonAlreadySwept,
+    #[msg("Previous airdrop season must be swept first")]
+    SeasonNotSwept,
+    #[msg("Invalid distribution")]
+    InvalidDistribution,
+    #[msg("Merkle proof does not match the distribution root")]
+    InvalidProof,
+    #[msg("Distribution entry already claimed")]
+    DistributionAlreadyClaimed,
+    #[msg("Distribution has expired")]
+    DistributionExpired,
+    #[msg("Distribution has not expired yet")]
+    DistributionNotExpired,
+    #[msg("Airdrop vault holds less than the allocated, unclaimed budget")]
+    VaultUnderfunded,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fresh() -> UserAirdrop {
+        UserAirdrop {
+            owner: Pubkey::new_from_array([1; 32]),
+            has_participated: true,
+            first_participation_time: 0,
+            total_profit: 0,
+            eligible_airdrop: 0,
+            has_claimed: false,
+            claimed_amount: 0,
+            claim_time: 0,
+        }
+    }
+
+    #[test]
+    fn eligibility_drops_to_zero_after_losing_the_profit() {
+        let mut user = fresh();
+        assert_eq!(user.sync_profit(50_000_000_000).unwrap(), 50_000_000_000);
+        assert_eq!(user.eligible_airdrop, 500_000_000_000);
+
+        // 之后输回去，净盈亏 ≤ 0
+        for net_pnl in [0, -20_000_000_000] {
+            assert_eq!(user.sync_profit(net_pnl).unwrap(), 0);
+            assert_eq!(user.total_profit, 0);
+            assert_eq!(user.eligible_airdrop, 0);
+        }
+    }
+
+    #[test]
+    fn resync_caps_and_nets_out_claims() {
+        let mut user = fresh();
+        user.sync_profit(5_000_000_000_000).unwrap();
+        assert_eq!(user.eligible_airdrop, MAX_AIRDROP_PER_USER);
+
+        user.claimed_amount = 3_000_000_000_000;
+        user.sync_profit(200_000_000_000).unwrap();
+        assert_eq!(user.eligible_airdrop, 0);
+        user.sync_profit(1_000_000_000_000).unwrap();
+        assert_eq!(user.eligible_airdrop, 7_000_000_000_000);
+    }
 }
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+// ============================================================
+// Merkle proofs for airdrop distributions
+// ============================================================
+//
+// Leaves and inner nodes are sha256 with distinct prefixes, so a leaf can
+// never be passed off as an inner node. Pairs are hashed in sorted order,
+// which means a proof is just the list of siblings from leaf to root. A
+// node without a sibling moves up a level unchanged.
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Deepest proof claim_distribution accepts (2^32 leaves).
+pub const MAX_PROOF_LEN: usize = 32;
+
+pub fn leaf(index: u32, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[
+        LEAF_PREFIX,
+        &index.to_le_bytes(),
+        claimant.as_ref(),
+        &amount.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, lo, hi]).to_bytes()
+}
+
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    proof.len() <= MAX_PROOF_LEN
+        && proof
+            .iter()
+            .fold(leaf, |node, sibling| hash_pair(&node, sibling))
+            == *root
+}
+
+/// Off-chain builder for distribution trees: `entries[i]` is leaf index `i`.
+pub struct MerkleTree {
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    pub fn new(entries: &[(Pubkey, u64)]) -> Self {
+        let mut layers = vec![entries
+            .iter()
+            .enumerate()
+            .map(|(i, (claimant, amount))| leaf(i as u32, claimant, *amount))
+            .collect::<Vec<_>>()];
+        while layers.last().is_some_and(|l| l.len() > 1) {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => hash_pair(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next);
+        }
+        Self { layers }
+    }
+
+    /// Root of the tree; all zero for no entries.
+    pub fn root(&self) -> [u8; 32] {
+        self.layers
+            .last()
+            .and_then(|l| l.first())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn proof(&self, index: u32) -> Vec<[u8; 32]> {
+        let mut i = index as usize;
+        let mut proof = Vec::new();
+        for layer in &self.layers[..self.layers.len() - 1] {
+            if let Some(sibling) = layer.get(i ^ 1) {
+                proof.push(*sibling);
+            }
+            i /= 2;
+        }
+        proof
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(n: u8) -> Vec<(Pubkey, u64)> {
+        (0..n)
+            .map(|i| (Pubkey::new_from_array([i + 1; 32]), 1_000 * (i as u64 + 1)))
+            .collect()
+    }
+
+    #[test]
+    fn every_leaf_verifies() {
+        for n in [1u8, 2, 3, 5, 8, 13] {
+            let list = entries(n);
+            let tree = MerkleTree::new(&list);
+            for (i, (claimant, amount)) in list.iter().enumerate() {
+                let i = i as u32;
+                assert!(verify(
+                    &tree.proof(i),
+                    &tree.root(),
+                    leaf(i, claimant, *amount)
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn altered_claims_do_not_verify() {
+        let list = entries(6);
+        let tree = MerkleTree::new(&list);
+        let root = tree.root();
+        let (claimant, amount) = list[2];
+        let proof = tree.proof(2);
+
+        assert!(!verify(&proof, &root, leaf(2, &claimant, amount + 1)));
+        assert!(!verify(&proof, &root, leaf(3, &claimant, amount)));
+        assert!(!verify(&proof, &root, leaf(2, &list[1].0, amount)));
+        assert!(!verify(&tree.proof(1), &root, leaf(2, &claimant, amount)));
+    }
+
+    #[test]
+    fn single_entry_root_is_its_leaf() {
+        let list = entries(1);
+        let tree = MerkleTree::new(&list);
+        assert_eq!(tree.root(), leaf(0, &list[0].0, list[0].1));
+        assert!(tree.proof(0).is_empty());
+    }
+}
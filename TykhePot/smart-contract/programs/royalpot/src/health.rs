@@ -0,0 +1,291 @@
+use crate::{
+    ErrorCode, PAUSE_AIRDROP, PAUSE_DEPOSITS, PAUSE_PRIZES, PAUSE_REFERRAL, PAUSE_TREASURY,
+    VAULT_KIND_AIRDROP, VAULT_KIND_PLATFORM, VAULT_KIND_PRIZE_ESCROW, VAULT_KIND_PROFIT_AIRDROP,
+    VAULT_KIND_REFERRAL, VAULT_KIND_RESERVE,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+
+// ============================================================
+// Vault health
+// ============================================================
+//
+// Payouts the referral vault cannot cover become PayoutIou balances instead
+// of being skipped. When the referral or airdrop vault drops below its floor
+// the matching feature switches off — deposits stop recording referrers,
+// use_free_bet is refused — until a later observation sees it back above.
+
+pub const VAULT_KINDS: usize = 5;
+
+#[account]
+pub struct VaultHealth {
+    /// Referral features switch off below this referral_vault balance
+    pub referral_floor: u64,
+    /// Free bets switch off below this airdrop_vault balance
+    pub airdrop_floor: u64,
+    /// Balances at the last observation, by VAULT_KIND_*
+    pub balances: [u64; VAULT_KINDS],
+    /// Outstanding PayoutIou (and, for referral, ReferralLedger) totals, by VAULT_KIND_*
+    pub owed: [u64; VAULT_KINDS],
+    pub referral_disabled: bool,
+    pub free_bet_disabled: bool,
+    pub last_checked: i64,
+    pub bump: u8,
+}
+
+impl VaultHealth {
+    pub const SIZE: usize = 8 + 8 + 8 * VAULT_KINDS + 8 * VAULT_KINDS + 1 + 1 + 8 + 1;
+
+    /// Record the referral vault balance and re-evaluate the referral switch.
+    pub fn observe_referral(&mut self, balance: u64, now: i64) {
+        self.balances[VAULT_KIND_REFERRAL as usize] = balance;
+        let disabled = balance < self.referral_floor;
+        if disabled != self.referral_disabled {
+            self.referral_disabled = disabled;
+            self.emit_changed(now);
+        }
+    }
+
+    /// Record the airdrop vault balance and re-evaluate the free-bet switch.
+    pub fn observe_airdrop(&mut self, balance: u64, now: i64) {
+        self.balances[VAULT_KIND_AIRDROP as usize] = balance;
+        let disabled = balance < self.airdrop_floor;
+        if disabled != self.free_bet_disabled {
+            self.free_bet_disabled = disabled;
+            self.emit_changed(now);
+        }
+    }
+
+    /// Owed minus last observed balance, by VAULT_KIND_*.
+    pub fn shortfalls(&self) -> [u64; VAULT_KINDS] {
+        let mut out = [0u64; VAULT_KINDS];
+        for (i, s) in out.iter_mut().enumerate() {
+            *s = self.owed[i].saturating_sub(self.balances[i]);
+        }
+        out
+    }
+
+    fn emit_changed(&self, now: i64) {
+        emit!(VaultHealthChanged {
+            referral_disabled: self.referral_disabled,
+            free_bet_disabled: self.free_bet_disabled,
+            timestamp: now,
+        });
+    }
+}
+
+/// Tokens a vault owes `beneficiary` (a token account) for payouts it could
+/// not cover at the time. One per (vault kind, beneficiary).
+#[account]
+pub struct PayoutIou {
+    /// VAULT_KIND_* of the paying vault
+    pub kind: u8,
+    pub beneficiary: Pubkey,
+    pub owed: u64,
+    pub bump: u8,
+}
+
+impl PayoutIou {
+    pub const SIZE: usize = 1 + 32 + 8 + 1;
+}
+
+/// TIMELOCK_OP_VAULT_FLOORS (and initialize_vault_health).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct VaultFloors {
+    pub referral_floor: u64,
+    pub airdrop_floor: u64,
+}
+
+/// Returned by check_vault_health.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct VaultHealthReport {
+    pub balances: [u64; VAULT_KINDS],
+    pub owed: [u64; VAULT_KINDS],
+    pub shortfalls: [u64; VAULT_KINDS],
+    pub referral_floor: u64,
+    pub airdrop_floor: u64,
+    pub referral_disabled: bool,
+    pub free_bet_disabled: bool,
+}
+
+/// Pause bit that also stops payouts from vault `kind`.
+pub fn pause_flag_for(kind: u8) -> Result<u8> {
+    Ok(match kind {
+        VAULT_KIND_PLATFORM => PAUSE_TREASURY,
+        VAULT_KIND_AIRDROP => PAUSE_AIRDROP,
+        VAULT_KIND_REFERRAL => PAUSE_REFERRAL,
+        VAULT_KIND_RESERVE => PAUSE_DEPOSITS,
+        VAULT_KIND_PRIZE_ESCROW => PAUSE_PRIZES,
+        VAULT_KIND_PROFIT_AIRDROP => PAUSE_AIRDROP,
+        _ => return err!(ErrorCode::InvalidVaultKind),
+    })
+}
+
+/// Add `amount` to `iou` (created on first use) and to the vault's owed total.
+pub fn record_iou(
+    health: &mut VaultHealth,
+    iou: &mut PayoutIou,
+    iou_bump: u8,
+    kind: u8,
+    beneficiary: Pubkey,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    iou.kind = kind;
+    iou.beneficiary = beneficiary;
+    iou.bump = iou_bump;
+    iou.owed = iou
+        .owed
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    health.owed[kind as usize] = health.owed[kind as usize]
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    emit!(IouRecorded {
+        kind,
+        beneficiary,
+        amount,
+        owed: iou.owed,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+// ------------------------------------------------------------
+// Handlers
+// ------------------------------------------------------------
+
+pub fn initialize_vault_health(
+    ctx: Context<crate::InitializeVaultHealth>,
+    floors: VaultFloors,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let health = &mut ctx.accounts.vault_health;
+    health.referral_floor = floors.referral_floor;
+    health.airdrop_floor = floors.airdrop_floor;
+    health.balances = [0u64; VAULT_KINDS];
+    health.owed = [0u64; VAULT_KINDS];
+    health.referral_disabled = false;
+    health.free_bet_disabled = false;
+    health.bump = ctx.bumps.vault_health;
+    health.observe_referral(ctx.accounts.referral_vault.amount, now);
+    health.observe_airdrop(ctx.accounts.airdrop_vault.amount, now);
+    health.balances[VAULT_KIND_RESERVE as usize] = ctx.accounts.reserve_vault.amount;
+    health.last_checked = now;
+    Ok(())
+}
+
+/// Permissionless: refresh balances and feature switches, and return the
+/// resulting report (also emitted as VaultHealthChecked).
+pub fn check_vault_health(ctx: Context<crate::CheckVaultHealth>) -> Result<VaultHealthReport> {
+    let now = Clock::get()?.unix_timestamp;
+    let health = &mut ctx.accounts.vault_health;
+    health.observe_referral(ctx.accounts.referral_vault.amount, now);
+    health.observe_airdrop(ctx.accounts.airdrop_vault.amount, now);
+    health.balances[VAULT_KIND_RESERVE as usize] = ctx.accounts.reserve_vault.amount;
+    health.last_checked = now;
+
+    let report = VaultHealthReport {
+        balances: health.balances,
+        owed: health.owed,
+        shortfalls: health.shortfalls(),
+        referral_floor: health.referral_floor,
+        airdrop_floor: health.airdrop_floor,
+        referral_disabled: health.referral_disabled,
+        free_bet_disabled: health.free_bet_disabled,
+    };
+    emit!(VaultHealthChecked {
+        report: report.clone(),
+        timestamp: now,
+    });
+    Ok(report)
+}
+
+/// Permissionless: pay as much of an IOU as its vault now holds; the rest
+/// stays owed.
+pub fn claim_iou(ctx: Context<crate::ClaimIou>, kind: u8) -> Result<()> {
+    let state = &ctx.accounts.global_state;
+    require!(
+        !state.paused(pause_flag_for(kind)?),
+        ErrorCode::ProtocolPaused
+    );
+    require!(
+        ctx.accounts.vault.key() == state.vault(kind)?,
+        ErrorCode::VaultMismatch
+    );
+    let now = Clock::get()?.unix_timestamp;
+    let iou = &mut ctx.accounts.iou;
+    let amount = iou.owed.min(ctx.accounts.vault.amount);
+    require!(amount > 0, ErrorCode::NothingToClaim);
+
+    let gs_bump = state.bump;
+    let gs_seeds: &[&[u8]] = &[b"global_state", &[gs_bump]];
+    let signer = &[gs_seeds];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.beneficiary.to_account_info(),
+                authority: state.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    iou.owed -= amount;
+    let health = &mut ctx.accounts.vault_health;
+    health.owed[kind as usize] = health.owed[kind as usize].saturating_sub(amount);
+    let balance = ctx.accounts.vault.amount - amount;
+    match kind {
+        VAULT_KIND_REFERRAL => health.observe_referral(balance, now),
+        VAULT_KIND_AIRDROP => health.observe_airdrop(balance, now),
+        _ => health.balances[kind as usize] = balance,
+    }
+
+    emit!(IouPaid {
+        kind,
+        beneficiary: iou.beneficiary,
+        amount,
+        remaining: iou.owed,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+// ------------------------------------------------------------
+// Events
+// ------------------------------------------------------------
+
+#[event]
+pub struct VaultHealthChecked {
+    pub report: VaultHealthReport,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultHealthChanged {
+    pub referral_disabled: bool,
+    pub free_bet_disabled: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IouRecorded {
+    pub kind: u8,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    /// Total now owed to beneficiary by this vault
+    pub owed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IouPaid {
+    pub kind: u8,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+    pub timestamp: i64,
+}
@@ -1,12 +1,22 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
-use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer, SetAuthority};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, Burn, CloseAccount, Mint, Token, TokenAccount, Transfer};
 
 pub mod airdrop;
+pub mod health;
+pub mod migration;
 pub mod randomness;
 pub mod staking;
+pub mod timelock;
+pub mod treasury;
 
-pub use randomness::{PendingDraw, VrfErrorCode, VrfState};
+pub use migration::{GLOBAL_STATE_VERSION, POOL_STATE_VERSION};
+pub use randomness::{PendingDraw, RandomnessSource, VrfErrorCode, VrfState};
+pub use timelock::{
+    AdminCouncil, AuthorityHandover, ClosePool, CouncilConfig, TimelockOp, TimelockQueue,
+    VaultRotation,
+};
 
 // ============================================================
 // Constants
@@ -49,29 +59,117 @@ pub const MIN_DAILY: u64 = 100_000_000_000; // 100 TPOT
 pub const FREE_BET_AMOUNT: u64 = 100_000_000_000; // 100 TPOT
 
 pub const VRF_TIMEOUT_SECONDS: i64 = 120; // 2 minutes timeout for VRF request
+pub const MAX_VRF_REQUESTS: u8 = 2; // per round: the first request and one retry
+pub const REVEAL_WINDOW: i64 = 600; // 10 min after round end to draw by VRF; then refund fallback
+pub const DRAW_COMMIT_LEAD: i64 = 300; // seed commit opens 5 min before the deposit lock
+pub const SLOT_DURATION_MS: i64 = 400; // nominal slot time, used to pin the entropy slot
+pub const REVEAL_SLOTS: u64 = 500; // reveal deadline after the entropy slot (SlotHashes keeps 512)
+pub const DRAW_COMMIT_BOND_BP: u64 = 1_000; // bond = 10% of the pot at commit, burned if the reveal is missed
 pub const VRF_CALLBACK_DISCRIMINATOR: [u8; 8] = [240, 114, 134, 56, 189, 82, 35, 25];
 
 pub const DEFAULT_TIMELOCK_DURATION: i64 = 86_400; // 24 hours default timelock
-pub const TIMELOCK_OP_NONE: u8 = 0;
-pub const TIMELOCK_OP_PAUSE: u8 = 1;
-pub const TIMELOCK_OP_UNPAUSE: u8 = 2;
+pub const TIMELOCK_GRACE_PERIOD: i64 = 7 * 86_400; // executable for 7 days after ETA
+pub const MAX_TIMELOCK_OPS: usize = 8;
+pub const MAX_COUNCIL_MEMBERS: usize = 16; // TimelockOp.approvals is a u16 bitmap
+pub const MAX_TREASURY_DESTINATIONS: usize = 8;
+
+// TimelockQueue operation types (payload in brackets)
+pub const TIMELOCK_OP_NONE: u8 = 0; // free slot
+pub const TIMELOCK_OP_PAUSE: u8 = 1; // [PAUSE_* flags u8]
+pub const TIMELOCK_OP_UNPAUSE: u8 = 2; // [PAUSE_* flags u8]
+pub const TIMELOCK_OP_CONFIG: u8 = 3; // [ProtocolParams]
+pub const TIMELOCK_OP_COUNCIL: u8 = 4; // [CouncilConfig]
+pub const TIMELOCK_OP_VAULT: u8 = 5; // [VaultRotation] — run by rotate_vault
+pub const TIMELOCK_OP_GUARDIAN: u8 = 6; // [guardian Pubkey; default = none]
+pub const TIMELOCK_OP_CLOSE_GLOBAL: u8 = 7; // [recipient Pubkey] — run by close_global_state
+pub const TIMELOCK_OP_CLOSE_POOL: u8 = 8; // [ClosePool] — run by close_pool_state
+pub const TIMELOCK_OP_TREASURY_CONFIG: u8 = 9; // [TreasuryConfig]
+pub const TIMELOCK_OP_TREASURY_WITHDRAW: u8 = 10; // [TreasuryWithdrawal] — run by treasury_withdraw
+pub const TIMELOCK_OP_VAULT_FLOORS: u8 = 11; // [VaultFloors]
+pub const TIMELOCK_OP_AUTHORITY: u8 = 12; // [AuthorityHandover] — run by propose_authority
+
+// GlobalState.pause_flags — one bit per subsystem
+pub const PAUSE_DEPOSITS: u8 = 1 << 0; // deposit, use_free_bet
+pub const PAUSE_DRAWS: u8 = 1 << 1; // commit/reveal, VRF draw, crank_draw
+pub const PAUSE_PRIZES: u8 = 1 << 2; // claim_prize_vesting, claim_round_prize
+pub const PAUSE_REFERRAL: u8 = 1 << 3; // claim_referral, withdraw_referral_earnings, claim_referee_bonus
+pub const PAUSE_STAKING: u8 = 1 << 4; // stake, release_stake, early_withdraw
+pub const PAUSE_AIRDROP: u8 = 1 << 5; // claim_free_airdrop, record_profit, claim_profit_airdrop
+pub const PAUSE_TREASURY: u8 = 1 << 6; // route_platform_fees, treasury_withdraw
+pub const PAUSE_ALL: u8 = (1 << 7) - 1;
+
+// Vault registry. Every protocol vault is a token account owned by global_state
+// at PDA [b"vault", &[kind]] ([b"vault", &[kind], nonce] after rotation).
+// Kinds up to PRIZE_ESCROW are recorded in GlobalState, PROFIT_AIRDROP in
+// AirdropState.vault — see registered_vault. Also VaultRotation.kind.
+pub const VAULT_KIND_PLATFORM: u8 = 0;
+pub const VAULT_KIND_AIRDROP: u8 = 1; // free-bet funding
+pub const VAULT_KIND_REFERRAL: u8 = 2;
+pub const VAULT_KIND_RESERVE: u8 = 3;
+pub const VAULT_KIND_PRIZE_ESCROW: u8 = 4;
+pub const VAULT_KIND_PROFIT_AIRDROP: u8 = 5; // profit airdrop, seasons, merkle distributions
 
 // Account sizes (bytes)
-// GlobalState: disc(8) + 7×Pubkey(224) + is_paused(1) + timelock_duration(8) +
-//              pending_operation(1) + timelock_release(8) + bump(1) + pad(5) = 256
-pub const GLOBAL_STATE_SIZE: usize = 8 + 224 + 1 + 8 + 1 + 8 + 1 + 5;
-// PoolState: disc(8)+pool_type(1)+round_number(8)+start(8)+end(8)+deposited(8)+
-//            free_bet_total(8)+regular_count(4)+free_count(4)+vault(32)+rollover(8)+bump(1)+pad(7)=105
-pub const POOL_STATE_SIZE: usize = 8 + 1 + 8 + 8 + 8 + 8 + 8 + 4 + 4 + 32 + 8 + 1 + 7;
-// UserDeposit: disc(8)+user(32)+pool_type(1)+round(8)+amount(8)+referrer(32)+bump(1)+pad(6) = 96
-pub const USER_DEPOSIT_SIZE: usize = 8 + 32 + 1 + 8 + 8 + 32 + 1 + 6;
-// FreeDeposit: disc(8)+user(32)+pool_type(1)+is_active(1)+amount(8)+referrer(32)+bump(1)+pad(7) = 90
-pub const FREE_DEPOSIT_SIZE: usize = 8 + 32 + 1 + 1 + 8 + 32 + 1 + 7;
+// GlobalState: disc(8) + version(1) + 7×Pubkey(224) + pause_flags(1) + timelock_duration(8) +
+//              reserved(9) + bump(1) + pad(5) + guardian(32) = 289
+pub const GLOBAL_STATE_SIZE: usize = 8 + 1 + 224 + 1 + 8 + 9 + 1 + 5 + 32;
+// PoolState: disc(8)+version(1)+pool_type(1)+round_number(8)+start(8)+end(8)+deposited(8)+
+//            free_bet_total(8)+regular_count(4)+free_count(4)+vault(32)+rollover(8)+bump(1)+
+//            draw_in_progress(1)+open_draw_results(4)+pad(2)+reserve_matched(8)+pending_payouts(8)+
+//            params(ROUND_PARAMS_SIZE) = 214
+pub const POOL_STATE_SIZE: usize =
+    8 + 1 + 1 + 8 + 8 + 8 + 8 + 8 + 4 + 4 + 32 + 8 + 1 + 1 + 4 + 2 + 8 + 8 + ROUND_PARAMS_SIZE;
+// RoundParams: 8×u64 rates(64)+min_participants(4)+lock_period(8)+duration(8)+min_deposit(8) = 92
+pub const ROUND_PARAMS_SIZE: usize = 64 + 4 + 8 + 8 + 8;
+// ProtocolParams: 8×u64 rates(64)+min_participants(4)+lock_period(8)+durations(24)+
+//                 min_deposits(24) = 124
+pub const PROTOCOL_PARAMS_SIZE: usize = 64 + 4 + 8 + 24 + 24;
+// ProtocolConfig: disc(8)+params(124)+updated_at(8)+bump(1) = 141
+pub const PROTOCOL_CONFIG_SIZE: usize = 8 + PROTOCOL_PARAMS_SIZE + 8 + 1;
+// AuthorityProposal: disc(8)+target(32)+proposed_authority(32)+eta(8)+bump(1) = 81
+pub const AUTHORITY_PROPOSAL_SIZE: usize = 8 + 32 + 32 + 8 + 1;
+// UserDeposit: disc(8)+user(32)+pool_type(1)+round(8)+amount(8)+referrer(32)+bump(1)+
+//              entry_index(4)+pad(2)+matched(8) = 104
+pub const USER_DEPOSIT_SIZE: usize = 8 + 32 + 1 + 8 + 8 + 32 + 1 + 4 + 2 + 8;
+// FreeDeposit: disc(8)+user(32)+pool_type(1)+is_active(1)+amount(8)+referrer(32)+bump(1)+
+//              entry_index(4)+consumed_round(8)+pad(3) = 98
+pub const FREE_DEPOSIT_SIZE: usize = 8 + 32 + 1 + 1 + 8 + 32 + 1 + 4 + 8 + 3;
 pub const AIRDROP_CLAIM_SIZE: usize = 8 + 32 + 1 + 1 + 6;
 pub const REFEREE_BONUS_CLAIM_SIZE: usize = 8 + 32 + 1 + 1 + 8 + 8 + 1 + 7;
+// ReferralLedger: disc(8)+referrer(32)+owed(8)+total_earned(8)+total_paid(8)+bump(1) = 65
+pub const REFERRAL_LEDGER_SIZE: usize = 8 + 32 + 8 + 8 + 8 + 1;
+// PlayerStats: disc(8)+player(32)+total_wagered(8)+free_bet_wagered(8)+total_won(8)+
+//              total_refunded(8)+rounds_played(4)+wins_by_tier(20)+net_pnl(8)+bump(1) = 113
+pub const PLAYER_STATS_SIZE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 4 + 20 + 8 + 1;
+// DrawCommit: disc(8)+pool_type(1)+round(8)+committer(32)+commitment(32)+commit_slot(8)+
+//             entropy_slot(8)+reveal_deadline_slot(8)+bond(8)+forfeited(1)+bump(1) = 115
+pub const DRAW_COMMIT_SIZE: usize = 8 + 1 + 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 1;
 // DrawResult: disc(8)+pool_type(1)+round(8)+top_winners(192)+top_amounts(48)+
-//             top_claimed(48)+draw_timestamp(8)+bump(1) = 314
-pub const DRAW_RESULT_SIZE: usize = 8 + 1 + 8 + 192 + 48 + 48 + 8 + 1;
+//             top_claimed(48)+draw_timestamp(8)+bump(1)+regular_count(4)+participant_count(4)+
+//             winner_indices(44)+lucky_amount_each(8)+universal_amount_each(8)+payer(32)+
+//             open_entries(4)+prize_claimed vec len(4) = 422, + 1 bit per participant
+pub const DRAW_RESULT_BASE_SIZE: usize =
+    8 + 1 + 8 + 192 + 48 + 48 + 8 + 1 + 4 + 4 + 44 + 8 + 8 + 32 + 4 + 4;
+// RoundRefund: disc(8)+pool_type(1)+round(8)+regular_count(4)+claimed_count(4)+
+//              total_deposited(8)+claimed_amount(8)+refunded_at(8)+bump(1)+
+//              reserve_returned(8) = 58
+pub const ROUND_REFUND_SIZE: usize = 8 + 1 + 8 + 4 + 4 + 8 + 8 + 8 + 1 + 8;
+// DrawProgress: disc(8)+pool_type(1)+round(8)+phase(1)+draw_seed(32)+cursor(4)+
+//               6×u64 amounts(48)+lucky_winners(160)+bump(1) = 263
+pub const DRAW_PROGRESS_SIZE: usize = 8 + 1 + 8 + 1 + 32 + 4 + 48 + 160 + 1;
+
+// DrawProgress.phase — crank_draw advances SETTLE → RECORD → FINALIZE
+pub const DRAW_PHASE_SETTLE: u8 = 0; // burn / platform fee / escrow not yet moved
+pub const DRAW_PHASE_RECORD: u8 = 1; // paging through participants from `cursor`
+pub const DRAW_PHASE_FINALIZE: u8 = 2; // all participants recorded; round not yet advanced
+
+// PlayerStats.wins_by_tier index
+pub const PRIZE_TIERS: usize = 5;
+pub const PRIZE_TIER_1ST: usize = 0;
+pub const PRIZE_TIER_2ND: usize = 1;
+pub const PRIZE_TIER_3RD: usize = 2;
+pub const PRIZE_TIER_LUCKY: usize = 3;
+pub const PRIZE_TIER_UNIVERSAL: usize = 4;
 
 // Staking/vesting (unchanged)
 pub const VESTING_DAYS: u64 = 20;
@@ -107,6 +205,11 @@ impl PoolType {
     }
 }
 
+/// DrawResult account size for a round with `participant_count` entries.
+pub fn draw_result_size(participant_count: u32) -> usize {
+    DRAW_RESULT_BASE_SIZE + (participant_count as usize).div_ceil(8)
+}
+
 pub fn pool_type_from_u8(v: u8) -> Result<PoolType> {
     match v {
         0 => Ok(PoolType::Min30),
@@ -116,13 +219,139 @@ pub fn pool_type_from_u8(v: u8) -> Result<PoolType> {
     }
 }
 
+// ============================================================
+// Protocol parameters
+// ============================================================
+
+/// Governable protocol parameters, held in ProtocolConfig. The constants
+/// above are the launch defaults. Per-pool arrays are indexed by pool_type.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ProtocolParams {
+    pub burn_rate: u64,
+    pub plat_rate: u64,
+    pub rollover_bp: u64,
+    pub prize_1st_bp: u64,
+    pub prize_2nd_each_bp: u64,
+    pub prize_3rd_each_bp: u64,
+    pub prize_lucky_each_bp: u64,
+    pub prize_universal_bp: u64,
+    pub min_participants: u32,
+    pub lock_period: i64,
+    pub durations: [i64; 3],
+    pub min_deposits: [u64; 3],
+}
+
+impl Default for ProtocolParams {
+    fn default() -> Self {
+        let pools = [PoolType::Min30, PoolType::Hourly, PoolType::Daily];
+        Self {
+            burn_rate: BURN_RATE,
+            plat_rate: PLAT_RATE,
+            rollover_bp: ROLLOVER_BP,
+            prize_1st_bp: PRIZE_1ST_BP,
+            prize_2nd_each_bp: PRIZE_2ND_EACH_BP,
+            prize_3rd_each_bp: PRIZE_3RD_EACH_BP,
+            prize_lucky_each_bp: PRIZE_LUCKY_EACH_BP,
+            prize_universal_bp: PRIZE_UNIVERSAL_BP,
+            min_participants: MIN_PARTICIPANTS,
+            lock_period: LOCK_PERIOD,
+            durations: pools.map(|pt| pt.duration()),
+            min_deposits: pools.map(|pt| pt.min_deposit()),
+        }
+    }
+}
+
+impl ProtocolParams {
+    /// Fees must leave a prize pool, and rollover + 1st + 2×2nd + 3×3rd +
+    /// 5×lucky + universal must be exactly 100% of it. Every share is a bp of
+    /// the prize pool itself (see begin_draw), so the rollover is exactly
+    /// rollover_bp of it plus rounding dust.
+    pub fn validate(&self) -> Result<()> {
+        let fees = self
+            .burn_rate
+            .checked_add(self.plat_rate)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(fees < BASE, ErrorCode::InvalidProtocolParams);
+
+        let split = [
+            (self.rollover_bp, 1u64),
+            (self.prize_1st_bp, 1),
+            (self.prize_2nd_each_bp, 2),
+            (self.prize_3rd_each_bp, 3),
+            (self.prize_lucky_each_bp, 5),
+            (self.prize_universal_bp, 1),
+        ]
+        .iter()
+        .try_fold(0u64, |acc, &(bp, n)| {
+            bp.checked_mul(n).and_then(|v| acc.checked_add(v))
+        })
+        .ok_or(ErrorCode::MathOverflow)?;
+        require!(split == BASE, ErrorCode::InvalidProtocolParams);
+
+        require!(
+            self.min_participants >= MIN_PARTICIPANTS,
+            ErrorCode::InvalidProtocolParams
+        );
+        for (&duration, &min_deposit) in self.durations.iter().zip(self.min_deposits.iter()) {
+            require!(
+                duration > 0 && self.lock_period >= 0 && self.lock_period < duration,
+                ErrorCode::InvalidProtocolParams
+            );
+            require!(min_deposit > 0, ErrorCode::InvalidProtocolParams);
+        }
+        Ok(())
+    }
+
+    /// The slice of these params one pool runs a round with.
+    pub fn for_pool(&self, pool_type: u8) -> Result<RoundParams> {
+        pool_type_from_u8(pool_type)?;
+        let i = pool_type as usize;
+        Ok(RoundParams {
+            burn_rate: self.burn_rate,
+            plat_rate: self.plat_rate,
+            rollover_bp: self.rollover_bp,
+            prize_1st_bp: self.prize_1st_bp,
+            prize_2nd_each_bp: self.prize_2nd_each_bp,
+            prize_3rd_each_bp: self.prize_3rd_each_bp,
+            prize_lucky_each_bp: self.prize_lucky_each_bp,
+            prize_universal_bp: self.prize_universal_bp,
+            min_participants: self.min_participants,
+            lock_period: self.lock_period,
+            duration: self.durations[i],
+            min_deposit: self.min_deposits[i],
+        })
+    }
+}
+
+/// Parameters a pool runs its current round with. Copied from ProtocolConfig
+/// when the round opens, so a config change never alters a round in flight.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RoundParams {
+    pub burn_rate: u64,
+    pub plat_rate: u64,
+    pub rollover_bp: u64,
+    pub prize_1st_bp: u64,
+    pub prize_2nd_each_bp: u64,
+    pub prize_3rd_each_bp: u64,
+    pub prize_lucky_each_bp: u64,
+    pub prize_universal_bp: u64,
+    pub min_participants: u32,
+    pub lock_period: i64,
+    pub duration: i64,
+    pub min_deposit: u64,
+}
+
 // ============================================================
 // Account Structs
 // ============================================================
 
-/// One-time global config. Written at initialize, never changed.
+/// Global config. Written at initialize; changed only through the
+/// TimelockQueue, except that the guardian can pause instantly.
 #[account]
 pub struct GlobalState {
+    /// Layout version (GLOBAL_STATE_VERSION); older layouts go through
+    /// migrate_global_state
+    pub version: u8,
     pub token_mint: Pubkey,
     /// Platform fee accumulator
     pub platform_fee_vault: Pubkey,
@@ -134,23 +363,73 @@ pub struct GlobalState {
     pub reserve_vault: Pubkey,
     /// Unvested top-prize escrow (holds 1st/2nd/3rd prize tokens until vested)
     pub prize_escrow_vault: Pubkey,
-    /// Protocol authority (can pause/unpause)
+    /// Protocol authority (schedules timelocked operations)
     pub authority: Pubkey,
-    /// Emergency pause flag
-    pub is_paused: bool,
+    /// PAUSE_* bits of the paused subsystems (was a single is_paused bool)
+    pub pause_flags: u8,
     /// Timelock duration in seconds (e.g., 86400 = 24 hours)
     pub timelock_duration: i64,
-    /// Pending operation type (0 = none, 1 = pause, 2 = unpause)
-    pub pending_operation: u8,
-    /// When the pending operation can be executed
-    pub timelock_release: i64,
+    /// Held the single pending operation before TimelockQueue; kept zeroed so
+    /// existing accounts keep their layout.
+    pub _reserved: [u8; 9],
     pub bump: u8,
     pub _padding: [u8; 5],
+    /// May set pause bits immediately via guardian_pause; cannot clear them
+    /// or move funds. Pubkey::default() = no guardian.
+    pub guardian: Pubkey,
+}
+
+impl GlobalState {
+    /// True if any of the subsystems in `flags` is paused.
+    pub fn paused(&self, flags: u8) -> bool {
+        self.pause_flags & flags != 0
+    }
+
+    /// The vault of VAULT_KIND_* `kind`.
+    pub fn vault(&self, kind: u8) -> Result<Pubkey> {
+        Ok(match kind {
+            VAULT_KIND_PLATFORM => self.platform_fee_vault,
+            VAULT_KIND_AIRDROP => self.airdrop_vault,
+            VAULT_KIND_REFERRAL => self.referral_vault,
+            VAULT_KIND_RESERVE => self.reserve_vault,
+            VAULT_KIND_PRIZE_ESCROW => self.prize_escrow_vault,
+            _ => return err!(ErrorCode::InvalidVaultKind),
+        })
+    }
+
+    fn set_vault(&mut self, kind: u8, vault: Pubkey) -> Result<()> {
+        match kind {
+            VAULT_KIND_PLATFORM => self.platform_fee_vault = vault,
+            VAULT_KIND_AIRDROP => self.airdrop_vault = vault,
+            VAULT_KIND_REFERRAL => self.referral_vault = vault,
+            VAULT_KIND_RESERVE => self.reserve_vault = vault,
+            VAULT_KIND_PRIZE_ESCROW => self.prize_escrow_vault = vault,
+            _ => return err!(ErrorCode::InvalidVaultKind),
+        }
+        Ok(())
+    }
+}
+
+/// Registered address of vault `kind`. `airdrop_state` is required for
+/// VAULT_KIND_PROFIT_AIRDROP.
+pub fn registered_vault(
+    state: &GlobalState,
+    airdrop_state: Option<&airdrop::AirdropState>,
+    kind: u8,
+) -> Result<Pubkey> {
+    match (kind, airdrop_state) {
+        (VAULT_KIND_PROFIT_AIRDROP, Some(airdrop_state)) => Ok(airdrop_state.vault),
+        (VAULT_KIND_PROFIT_AIRDROP, None) => err!(ErrorCode::InvalidVaultKind),
+        _ => state.vault(kind),
+    }
 }
 
 /// Per-pool state. Three PDAs: 30min / hourly / daily.
 #[account]
 pub struct PoolState {
+    /// Layout version (POOL_STATE_VERSION); older layouts go through
+    /// migrate_pool_state
+    pub version: u8,
     pub pool_type: u8,
     pub round_number: u64,
     pub round_start_time: i64,
@@ -163,7 +442,59 @@ pub struct PoolState {
     /// Rollover from previous successful draw (already in vault)
     pub rollover: u64,
     pub bump: u8,
-    pub _padding: [u8; 7],
+    /// Set when a draw starts, cleared by crank_draw once the round is finalized.
+    /// Blocks execute_refund for a round whose winners are already fixed.
+    pub draw_in_progress: bool,
+    /// DrawResult accounts of this pool not yet closed by close_draw_result
+    pub open_draw_results: u32,
+    pub _padding: [u8; 2],
+    /// Daily-pool reserve matching pulled into this round (included in
+    /// total_deposited); returned to reserve_vault if the round is refunded.
+    pub reserve_matched: u64,
+    /// Tokens in pool_vault owed to earlier rounds: unclaimed refunds and
+    /// unclaimed lucky/universal prizes.
+    pub pending_payouts: u64,
+    /// Parameters of the current round, snapshotted from ProtocolConfig
+    /// whenever a round opens.
+    pub params: RoundParams,
+}
+
+/// Governable protocol parameters. Changed only through a TIMELOCK_OP_CONFIG
+/// operation in the TimelockQueue; pools pick up the new values when their
+/// next round opens.
+#[account]
+pub struct ProtocolConfig {
+    pub params: ProtocolParams,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+/// Pending authority handover for GlobalState, StakingState or AirdropState.
+/// One per target; proposing again replaces it.
+#[account]
+pub struct AuthorityProposal {
+    /// The state account whose authority changes
+    pub target: Pubkey,
+    pub proposed_authority: Pubkey,
+    /// accept_authority allowed from here (proposal + timelock_duration, or
+    /// immediately for a handover that already waited in the TimelockQueue)
+    pub eta: i64,
+    pub bump: u8,
+}
+
+impl PoolState {
+    /// What pool_vault must hold: the current round's entries and rollover
+    /// plus everything still owed to earlier rounds.
+    pub fn accounted_total(&self) -> Result<u64> {
+        Ok(self
+            .total_deposited
+            .checked_add(self.free_bet_total)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_add(self.rollover)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_add(self.pending_payouts)
+            .ok_or(ErrorCode::MathOverflow)?)
+    }
 }
 
 /// One deposit per user per pool per round.
@@ -174,10 +505,16 @@ pub struct UserDeposit {
     pub round_number: u64,
     pub amount: u64,
     /// Referrer's token account (Pubkey::default = no referrer).
-    /// Cleared to default once referral has been paid via claim_referral().
+    /// Cleared to default once claim_referral() has credited the referral.
     pub referrer: Pubkey,
     pub bump: u8,
-    pub _padding: [u8; 6],
+    /// Position among this round's regular deposits (0-based, in deposit order).
+    /// This is the participant index pick_winners draws from.
+    pub entry_index: u32,
+    pub _padding: [u8; 2],
+    /// Daily-pool reserve matching credited for this deposit (0 for other pools).
+    /// Not refundable to the user — goes back to reserve_vault on refund.
+    pub matched: u64,
 }
 
 /// Free-bet entry. Persists across refunded rounds (is_active stays true).
@@ -191,7 +528,13 @@ pub struct FreeDeposit {
     /// Always Pubkey::default() — free bets carry no referral obligation.
     pub referrer: Pubkey,
     pub bump: u8,
-    pub _padding: [u8; 7],
+    /// Position among the pool's free-bet entries (0-based, in activation order).
+    /// Participant index = regular_count + entry_index.
+    pub entry_index: u32,
+    /// Round whose successful draw consumed this entry (0 while active).
+    /// Binds the entry to that round's DrawResult for claim_round_prize.
+    pub consumed_round: u64,
+    pub _padding: [u8; 3],
 }
 
 /// Created once when user calls claim_free_airdrop.
@@ -216,8 +559,133 @@ pub struct RefereeBonusClaim {
     pub _padding: [u8; 7],
 }
 
-/// Created during execute_draw for every successful (≥12-participant) round.
-/// Tracks the 6 top-prize winners and their individual vested claims.
+/// What the referral vault owes one referrer. claim_referral credits it
+/// after every successful round; withdraw_referral_earnings pays it out as
+/// far as the vault allows.
+#[account]
+pub struct ReferralLedger {
+    /// Referrer token account (UserDeposit.referrer)
+    pub referrer: Pubkey,
+    pub owed: u64,
+    pub total_earned: u64,
+    pub total_paid: u64,
+    pub bump: u8,
+}
+
+/// Lifetime play record of one wallet, kept by deposit, use_free_bet,
+/// claim_prize_vesting, claim_round_prize and claim_refund, so the profit
+/// airdrop and loyalty features can read it instead of off-chain totals.
+#[account]
+pub struct PlayerStats {
+    pub player: Pubkey,
+    /// Own tokens deposited
+    pub total_wagered: u64,
+    /// Airdrop-funded free-bet stakes; not part of net_pnl
+    pub free_bet_wagered: u64,
+    /// Prize tokens received — vested top prizes count as each slice is paid
+    pub total_won: u64,
+    pub total_refunded: u64,
+    /// Deposits plus free bets
+    pub rounds_played: u32,
+    /// Wins by PRIZE_TIER_*
+    pub wins_by_tier: [u32; PRIZE_TIERS],
+    /// total_won + total_refunded - total_wagered
+    pub net_pnl: i64,
+    pub bump: u8,
+}
+
+impl PlayerStats {
+    /// Set the identity fields; a no-op on an account that already has them.
+    fn open(&mut self, player: Pubkey, bump: u8) {
+        self.player = player;
+        self.bump = bump;
+    }
+
+    fn record_entry(&mut self, amount: u64, free_bet: bool) -> Result<()> {
+        if free_bet {
+            self.free_bet_wagered = self
+                .free_bet_wagered
+                .checked_add(amount)
+                .ok_or(ErrorCode::MathOverflow)?;
+        } else {
+            self.total_wagered = self
+                .total_wagered
+                .checked_add(amount)
+                .ok_or(ErrorCode::MathOverflow)?;
+            self.adjust_pnl(amount, false)?;
+        }
+        self.rounds_played = self
+            .rounds_played
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// `tier` is Some on the first payout of a prize, None on later vesting slices.
+    fn record_win(&mut self, amount: u64, tier: Option<usize>) -> Result<()> {
+        self.total_won = self
+            .total_won
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        if let Some(tier) = tier {
+            self.wins_by_tier[tier] = self.wins_by_tier[tier]
+                .checked_add(1)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+        self.adjust_pnl(amount, true)
+    }
+
+    fn record_refund(&mut self, amount: u64) -> Result<()> {
+        self.total_refunded = self
+            .total_refunded
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.adjust_pnl(amount, true)
+    }
+
+    fn adjust_pnl(&mut self, amount: u64, gain: bool) -> Result<()> {
+        let amount = i64::try_from(amount).map_err(|_| ErrorCode::MathOverflow)?;
+        self.net_pnl = if gain {
+            self.net_pnl.checked_add(amount)
+        } else {
+            self.net_pnl.checked_sub(amount)
+        }
+        .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// Commit-reveal record for one round's draw seed.
+/// Created by commit_draw_seed before the deposit lock, closed by reveal_and_draw
+/// (or close_draw_commit if the reveal never came).
+/// commitment = sha256(seed); the seed is later mixed with the SlotHashes entry
+/// of `entropy_slot`, which did not exist at commit time.
+/// The committer's TPOT bond sits in the round's draw_bond escrow meanwhile.
+#[account]
+pub struct DrawCommit {
+    pub pool_type: u8,
+    pub round_number: u64,
+    pub committer: Pubkey,
+    pub commitment: [u8; 32],
+    pub commit_slot: u64,
+    /// Estimated slot at round end; its hash is the reveal's entropy
+    pub entropy_slot: u64,
+    /// entropy_slot + REVEAL_SLOTS: last slot the reveal is accepted, while
+    /// the entropy slot's hash is still in SlotHashes
+    pub reveal_deadline_slot: u64,
+    /// TPOT escrowed in draw_bond
+    pub bond: u64,
+    /// Set when close_draw_commit burned the bond; the record stays until the
+    /// round is refunded so execute_refund can see the reveal was missed
+    pub forfeited: bool,
+    pub bump: u8,
+}
+
+/// Created during reveal_and_draw / execute_draw_vrf for every successful (≥12-participant) round.
+/// Tracks the 6 top-prize winners and their individual vested claims, plus the
+/// lucky/universal entitlements every other participant pulls via claim_round_prize.
+/// top_winners are filled in as crank_draw pages past them; draw_timestamp stays 0
+/// until the draw is finalized, and vesting and claims start from it.
 /// top_winners[0]      = 1st prize winner
 /// top_winners[1..2]   = 2nd prize winners (×2)
 /// top_winners[3..5]   = 3rd prize winners (×3)
@@ -232,6 +700,89 @@ pub struct DrawResult {
     pub top_claimed: [u64; 6],
     pub draw_timestamp: i64,
     pub bump: u8,
+    /// Participant counts frozen at draw start (index < regular_count → UserDeposit)
+    pub regular_count: u32,
+    pub participant_count: u32,
+    /// randomness::pick_winners output: [1st, 2nd×2, 3rd×3, lucky×5]
+    pub winner_indices: [u32; 11],
+    pub lucky_amount_each: u64,
+    /// Paid to every participant that is not one of the 11 winners
+    pub universal_amount_each: u64,
+    /// Paid the rent at draw start; close_draw_result returns it here
+    pub payer: Pubkey,
+    /// Deposit PDAs of this round not yet closed. claim_round_prize and
+    /// claim_referral need this record, so it outlives every deposit.
+    pub open_entries: u32,
+    /// Bit i set once participant i has claimed its lucky/universal prize
+    pub prize_claimed: Vec<u8>,
+}
+
+impl DrawResult {
+    /// Lucky or universal prize owed to participant `index`; 0 for top winners,
+    /// who are paid through claim_prize_vesting instead.
+    pub fn round_prize(&self, index: u32) -> u64 {
+        match self.winner_indices.iter().position(|&w| w == index) {
+            Some(slot) if slot < 6 => 0,
+            Some(_) => self.lucky_amount_each,
+            None => self.universal_amount_each,
+        }
+    }
+
+    pub fn is_prize_claimed(&self, index: u32) -> bool {
+        self.prize_claimed[(index / 8) as usize] & (1 << (index % 8)) != 0
+    }
+
+    pub fn set_prize_claimed(&mut self, index: u32) {
+        self.prize_claimed[(index / 8) as usize] |= 1 << (index % 8);
+    }
+
+    /// Nothing left for participant `index` to pull via claim_round_prize.
+    pub fn round_prize_settled(&self, index: u32) -> bool {
+        self.round_prize(index) == 0 || self.is_prize_claimed(index)
+    }
+}
+
+/// Outcome record for a refunded round, created by execute_refund.
+/// Its existence is what entitles each of the round's UserDeposits to claim_refund.
+#[account]
+pub struct RoundRefund {
+    pub pool_type: u8,
+    pub round_number: u64,
+    pub regular_count: u32,
+    pub claimed_count: u32,
+    pub total_deposited: u64,
+    pub claimed_amount: u64,
+    pub refunded_at: i64,
+    pub bump: u8,
+    /// Reserve matching sent back to reserve_vault when the round was refunded
+    pub reserve_returned: u64,
+}
+
+/// Resumable state of one round's draw, so pools too large for a single
+/// transaction can be settled across many crank_draw calls.
+/// Created when the winners are selected, closed when the round is finalized.
+/// Participant index i is UserDeposit.entry_index for i < DrawResult.regular_count,
+/// otherwise FreeDeposit.entry_index + regular_count.
+#[account]
+pub struct DrawProgress {
+    pub pool_type: u8,
+    pub round_number: u64,
+    /// DRAW_PHASE_* constant
+    pub phase: u8,
+    pub draw_seed: [u8; 32],
+    /// Next participant index to be recorded
+    pub cursor: u32,
+    pub total_pool: u64,
+    pub burn_amount: u64,
+    pub platform_amount: u64,
+    pub rollover_amount: u64,
+    /// 1st + 2×2nd + 3×3rd, moved to prize_escrow_vault in the SETTLE phase
+    pub top_prize_total: u64,
+    /// 5×lucky + (total−11)×universal, left in pool_vault for claim_round_prize
+    pub round_prize_total: u64,
+    /// Filled in as crank_draw pages past them; reported in DrawExecuted
+    pub lucky_winners: [Pubkey; 5],
+    pub bump: u8,
 }
 
 // ============================================================
@@ -270,7 +821,7 @@ pub enum ErrorCode {
     AirdropVaultMismatch,
     #[msg("Token mint mismatch")]
     MintMismatch,
-    #[msg("Pool has enough participants — use execute_draw instead")]
+    #[msg("Pool has enough participants — use reveal_and_draw or execute_draw_vrf instead")]
     ShouldUseDraw,
     #[msg("Pool does not have enough participants — use execute_refund instead")]
     ShouldUseRefund,
@@ -310,6 +861,88 @@ pub enum ErrorCode {
     NoRefereeEligibility,
     #[msg("Unauthorized: only user can claim their own referee bonus")]
     UnauthorizedClaimer,
+    #[msg("Draw seed must be committed before the deposit lock starts")]
+    CommitWindowClosed,
+    #[msg("Reveal window has expired — use close_draw_commit, then execute_refund")]
+    RevealWindowExpired,
+    #[msg("Reveal window is still open")]
+    RevealWindowOpen,
+    #[msg("Round has an unrevealed draw commit — close it with close_draw_commit first")]
+    DrawCommitOutstanding,
+    #[msg("A draw is in progress for this round — crank it with crank_draw")]
+    DrawInProgress,
+    #[msg("Draw has not been finalized yet")]
+    DrawNotFinalized,
+    #[msg("Participant has no lucky or universal prize in this round")]
+    NoRoundPrize,
+    #[msg("Round prize already claimed")]
+    RoundPrizeAlreadyClaimed,
+    #[msg("Payout account is not the depositor's associated token account")]
+    NotDepositorAta,
+    #[msg("Participant account appears more than once")]
+    DuplicateParticipant,
+    #[msg("Payout token account is not owned by the depositor or has the wrong mint")]
+    PayoutAccountMismatch,
+    #[msg("Pool vault balance does not match the pool's accounted total")]
+    PoolVaultImbalance,
+    #[msg("Referral for this deposit has not been paid yet")]
+    ReferralUnpaid,
+    #[msg("Round prize for this entry has not been claimed yet")]
+    RoundPrizeUnclaimed,
+    #[msg("Free bet is still active")]
+    FreeBetStillActive,
+    #[msg("Top prizes are still vesting")]
+    TopPrizesOutstanding,
+    #[msg("Deposit accounts of this round are still open")]
+    DepositsOutstanding,
+    #[msg("Invalid protocol parameters: fees or prize splits do not add up")]
+    InvalidProtocolParams,
+    #[msg("Timelock queue is full — execute or cancel an operation first")]
+    TimelockQueueFull,
+    #[msg("No scheduled operation with this id")]
+    TimelockOpNotFound,
+    #[msg("Scheduled operation has expired — cancel it and schedule again")]
+    TimelockOpExpired,
+    #[msg("Payload does not match the scheduled operation")]
+    PayloadHashMismatch,
+    #[msg("Invalid operation type or payload")]
+    InvalidTimelockPayload,
+    #[msg("Council needs 1 ≤ threshold ≤ members ≤ 16 distinct, non-default members")]
+    InvalidCouncil,
+    #[msg("Council member already approved this operation")]
+    AlreadyApproved,
+    #[msg("Operation does not have enough council approvals")]
+    InsufficientApprovals,
+    #[msg("Account is not a GlobalState, StakingState or AirdropState")]
+    InvalidAuthorityTarget,
+    #[msg("Pool still holds tokens, entries or unsettled draws")]
+    PoolNotEmpty,
+    #[msg("All pools must be closed first")]
+    PoolsStillOpen,
+    #[msg("Protocol vaults must be emptied first")]
+    VaultsNotEmpty,
+    #[msg("Account is already on the current layout")]
+    AlreadyMigrated,
+    #[msg("Account data matches no known layout")]
+    UnknownAccountLayout,
+    #[msg("Unknown vault kind")]
+    InvalidVaultKind,
+    #[msg("Treasury needs ≤ 8 distinct destinations, epoch_duration > 0 and routing ≤ 100%")]
+    InvalidTreasuryConfig,
+    #[msg("Destination is not on the treasury whitelist")]
+    DestinationNotWhitelisted,
+    #[msg("Withdrawal exceeds the treasury's per-epoch limit")]
+    TreasuryRateLimited,
+    #[msg("Treasury has less withdrawable balance than requested")]
+    InsufficientTreasuryBalance,
+    #[msg("Free bets are switched off until the airdrop vault is refilled")]
+    FreeBetDisabled,
+    #[msg("Airdrop vault cannot cover a free bet")]
+    FreeBetUnfunded,
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+    #[msg("Account is not the recorded winner of this prize")]
+    WinnerMismatch,
 }
 
 // ============================================================
@@ -335,6 +968,19 @@ pub struct DrawExecuted {
     pub burn_amount: u64,
     pub platform_amount: u64,
     pub rollover_amount: u64,
+    /// Final draw randomness. randomness::pick_winners(draw_seed, participant_count)
+    /// reproduces the participant indices of [top_winners, lucky_winners].
+    pub draw_seed: [u8; 32],
+    pub timestamp: i64,
+}
+
+/// Winners selected; payouts follow via crank_draw.
+#[event]
+pub struct DrawStarted {
+    pub pool_type: u8,
+    pub round_number: u64,
+    pub participant_count: u32,
+    pub winner_indices: [u32; 11],
     pub draw_seed: [u8; 32],
     pub timestamp: i64,
 }
@@ -346,6 +992,17 @@ pub struct RoundRefunded {
     pub regular_refunded: u32,
     pub free_carried_over: u32,
     pub total_refunded: u64,
+    /// Reserve matching returned to reserve_vault (daily pool)
+    pub reserve_returned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub pool_type: u8,
+    pub round_number: u64,
+    pub user: Pubkey,
+    pub amount: u64,
     pub timestamp: i64,
 }
 
@@ -378,6 +1035,37 @@ pub struct PrizeVestingClaimed {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct RoundPrizeClaimed {
+    pub pool_type: u8,
+    pub round_number: u64,
+    pub participant_index: u32,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DrawSeedCommitted {
+    pub pool_type: u8,
+    pub round_number: u64,
+    pub commitment: [u8; 32],
+    pub commit_slot: u64,
+    pub entropy_slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DrawCommitClosed {
+    pub pool_type: u8,
+    pub round_number: u64,
+    pub committer: Pubkey,
+    /// true = the bond was burned instead of returned to the committer
+    pub forfeited: bool,
+    pub bond: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct VrfRequested {
     pub pool_type: u8,
@@ -396,35 +1084,84 @@ pub struct VrfFulfilled {
 }
 
 #[event]
-pub struct RefereeBonusClaimed {
+pub struct ProtocolConfigUpdated {
+    pub params: ProtocolParams,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub target: Pubkey,
+    pub current_authority: Pubkey,
+    pub proposed_authority: Pubkey,
+    pub eta: i64,
+}
+
+#[event]
+pub struct AuthorityAccepted {
+    pub target: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct GuardianPaused {
+    pub guardian: Pubkey,
+    pub flags: u8,
+    pub pause_flags: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GlobalStateClosed {
+    pub recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultRotated {
+    pub kind: u8,
+    pub old_vault: Pubkey,
+    pub new_vault: Pubkey,
+    /// Balance moved from old_vault (0 for a platform vault not owned by global_state)
+    pub swept: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolStateClosed {
+    pub pool_type: u8,
+    pub round_number: u64,
+    pub recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralCredited {
+    pub referrer: Pubkey,
     pub user: Pubkey,
     pub pool_type: u8,
     pub round_number: u64,
-    pub deposit_amount: u64,
-    pub bonus_amount: u64,
+    pub amount: u64,
+    /// Ledger balance after the credit
+    pub owed: u64,
 }
 
-// ============================================================
-// Helper: Pick 11 distinct winner indices via partial Fisher-Yates
-// Uses LCG seeded from draw_seed[0..8].
-// Returns [1st, 2nd_a, 2nd_b, 3rd_a, 3rd_b, 3rd_c, lucky×5]
-// ============================================================
+#[event]
+pub struct ReferralEarningsWithdrawn {
+    pub referrer: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+    pub timestamp: i64,
+}
 
-fn pick_winner_indices(seed: [u8; 32], total: usize) -> [usize; 11] {
-    // Needs total >= 11 (guaranteed by MIN_PARTICIPANTS = 12)
-    let mut pool: Vec<usize> = (0..total).collect();
-    let mut rng = u64::from_le_bytes(seed[0..8].try_into().unwrap());
-    for i in 0..11 {
-        // LCG step
-        rng = rng
-            .wrapping_mul(6_364_136_223_846_793_005u64)
-            .wrapping_add(1_442_695_040_888_963_407u64);
-        let j = i + (rng >> 33) as usize % (total - i);
-        pool.swap(i, j);
-    }
-    let mut result = [0usize; 11];
-    result.copy_from_slice(&pool[0..11]);
-    result
+#[event]
+pub struct RefereeBonusClaimed {
+    pub user: Pubkey,
+    pub pool_type: u8,
+    pub round_number: u64,
+    pub deposit_amount: u64,
+    pub bonus_amount: u64,
 }
 
 // ============================================================
@@ -440,189 +1177,729 @@ pub mod royalpot {
     // ----------------------------------------------------------
 
     /// Initialize global config. Called once at deployment.
-    /// After this, no admin controls exist — everything is rule-based.
-    pub fn initialize(
-        ctx: Context<Initialize>,
-        platform_fee_vault: Pubkey,
-        referral_vault: Pubkey,
-        reserve_vault: Pubkey,
-        prize_escrow_vault: Pubkey,
-        timelock_duration: Option<i64>,
-    ) -> Result<()> {
+    /// Later changes go through the TimelockQueue (schedule_op → execute_op,
+    /// with AdminCouncil approvals once a council exists); only the guardian
+    /// acts immediately, and only to pause.
+    /// Creates the five GlobalState vaults as token accounts of `token_mint`
+    /// at PDAs [b"vault", &[VAULT_KIND_*]], owned by global_state.
+    pub fn initialize(ctx: Context<Initialize>, timelock_duration: Option<i64>) -> Result<()> {
         let state = &mut ctx.accounts.global_state;
-        let global_state_pda = ctx.accounts.global_state.key();
+        state.version = GLOBAL_STATE_VERSION;
         state.token_mint = ctx.accounts.token_mint.key();
-        state.platform_fee_vault = platform_fee_vault;
+        state.platform_fee_vault = ctx.accounts.platform_fee_vault.key();
         state.airdrop_vault = ctx.accounts.airdrop_vault.key();
-        state.referral_vault = referral_vault;
-        state.reserve_vault = reserve_vault;
-        state.prize_escrow_vault = prize_escrow_vault;
+        state.referral_vault = ctx.accounts.referral_vault.key();
+        state.reserve_vault = ctx.accounts.reserve_vault.key();
+        state.prize_escrow_vault = ctx.accounts.prize_escrow_vault.key();
         state.authority = ctx.accounts.payer.key();
-        state.is_paused = false;
+        state.pause_flags = 0;
         state.timelock_duration = timelock_duration.unwrap_or(DEFAULT_TIMELOCK_DURATION);
-        state.pending_operation = TIMELOCK_OP_NONE;
-        state.timelock_release = 0;
+        state._reserved = [0u8; 9];
         state.bump = ctx.bumps.global_state;
         state._padding = [0u8; 5];
-
-        // Set AIRDROP_VAULT authority to global_state PDA
-        // This allows the program to transfer tokens from the vault
-        msg!("Setting AIRDROP_VAULT authority to global_state PDA");
-        token::set_authority(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                SetAuthority {
-                    account: ctx.accounts.airdrop_vault.to_account_info(),
-                    current_authority: ctx.accounts.payer.to_account_info(),
-                    new_authority: global_state_pda,
-                },
-            ),
-        )?;
-
+        state.guardian = Pubkey::default();
         Ok(())
     }
 
-    /// Emergency pause - stops all deposits and draws
-    /// Requires timelock: first call schedule_pause, then call execute_pause after timelock expires
-    pub fn schedule_pause(ctx: Context<Pause>) -> Result<()> {
-        require!(
-            ctx.accounts.global_state.authority == ctx.accounts.authority.key(),
-            ErrorCode::Unauthorized
-        );
-        require!(
-            ctx.accounts.global_state.pending_operation == TIMELOCK_OP_NONE,
-            ErrorCode::TimelockAlreadyPending
-        );
+    /// Create ProtocolConfig with the launch defaults. Authority only, once.
+    pub fn initialize_protocol_config(ctx: Context<InitializeProtocolConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.protocol_config;
+        config.params = ProtocolParams::default();
+        config.updated_at = Clock::get()?.unix_timestamp;
+        config.bump = ctx.bumps.protocol_config;
+        Ok(())
+    }
 
-        let clock = Clock::get()?;
-        ctx.accounts.global_state.pending_operation = TIMELOCK_OP_PAUSE;
-        ctx.accounts.global_state.timelock_release =
-            clock.unix_timestamp + ctx.accounts.global_state.timelock_duration;
+    /// Create the TimelockQueue. Authority only, once.
+    pub fn initialize_timelock_queue(ctx: Context<InitializeTimelockQueue>) -> Result<()> {
+        let queue = &mut ctx.accounts.timelock_queue;
+        queue.next_id = 0;
+        queue.ops = [TimelockOp::default(); MAX_TIMELOCK_OPS];
+        queue.bump = ctx.bumps.timelock_queue;
+        Ok(())
+    }
 
+    /// Create the AdminCouncil. Takes effect once GlobalState.authority is
+    /// handed to the council PDA via propose_authority / accept_authority.
+    pub fn initialize_admin_council(
+        ctx: Context<InitializeAdminCouncil>,
+        council: CouncilConfig,
+    ) -> Result<()> {
+        council.validate()?;
+        let admin_council = &mut ctx.accounts.admin_council;
+        admin_council.members = council.members.clone();
+        admin_council.threshold = council.threshold;
+        admin_council.bump = ctx.bumps.admin_council;
+
+        emit!(timelock::AdminCouncilUpdated {
+            members: council.members,
+            threshold: council.threshold,
+        });
         Ok(())
     }
 
-    /// Execute pause after timelock expires
-    pub fn execute_pause(ctx: Context<Pause>) -> Result<()> {
-        require!(
-            ctx.accounts.global_state.authority == ctx.accounts.authority.key(),
-            ErrorCode::Unauthorized
-        );
-        require!(
-            ctx.accounts.global_state.pending_operation == TIMELOCK_OP_PAUSE,
-            ErrorCode::NoPendingOperation
-        );
+    /// Queue an admin operation (pause, unpause, parameter change, council
+    /// change, vault rotation). It becomes executable after
+    /// GlobalState.timelock_duration and stays so for TIMELOCK_GRACE_PERIOD.
+    /// Only the payload's hash is stored; the full payload is in the event.
+    /// A council member scheduling counts as its first approval.
+    pub fn schedule_op(ctx: Context<ScheduleOp>, op_type: u8, payload: Vec<u8>) -> Result<()> {
+        let admin = admin_signer(
+            &ctx.accounts.global_state,
+            &ctx.accounts.authority.key(),
+            ctx.accounts.admin_council.as_deref(),
+        )?;
+        validate_op_payload(op_type, &payload)?;
 
         let clock = Clock::get()?;
-        require!(
-            clock.unix_timestamp >= ctx.accounts.global_state.timelock_release,
-            ErrorCode::TimelockNotExpired
-        );
+        let eta = clock
+            .unix_timestamp
+            .checked_add(ctx.accounts.global_state.timelock_duration)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let approvals = match admin {
+            AdminSigner::Authority => 0,
+            AdminSigner::Member(index) => 1u16 << index,
+        };
+        let op = ctx
+            .accounts
+            .timelock_queue
+            .schedule(op_type, &payload, eta, approvals)?;
+
+        emit!(timelock::TimelockOpScheduled {
+            id: op.id,
+            op_type,
+            payload,
+            payload_hash: op.payload_hash,
+            eta: op.eta,
+            expires_at: op.expires_at,
+        });
+        Ok(())
+    }
 
-        ctx.accounts.global_state.is_paused = true;
-        ctx.accounts.global_state.pending_operation = TIMELOCK_OP_NONE;
-        ctx.accounts.global_state.timelock_release = 0;
+    /// Approve a scheduled operation as a council member.
+    pub fn approve_op(ctx: Context<ApproveOp>, id: u64) -> Result<()> {
+        let member = ctx.accounts.member.key();
+        let index = match admin_signer(
+            &ctx.accounts.global_state,
+            &member,
+            Some(&ctx.accounts.admin_council),
+        )? {
+            AdminSigner::Member(index) => index,
+            AdminSigner::Authority => return err!(ErrorCode::Unauthorized),
+        };
+        let op = ctx.accounts.timelock_queue.approve(id, index)?;
 
+        emit!(timelock::TimelockOpApproved {
+            id,
+            member,
+            approvals: op.approvals,
+        });
         Ok(())
     }
 
-    /// Schedule unpause operation
-    pub fn schedule_unpause(ctx: Context<Unpause>) -> Result<()> {
-        require!(
-            ctx.accounts.global_state.authority == ctx.accounts.authority.key(),
-            ErrorCode::Unauthorized
-        );
+    /// Execute a scheduled operation inside its [eta, expires_at] window.
+    /// `payload` must be the bytes it was scheduled with. Under a council the
+    /// operation also needs `threshold` approvals.
+    pub fn execute_op(ctx: Context<ExecuteOp>, id: u64, payload: Vec<u8>) -> Result<()> {
+        let clock = Clock::get()?;
+        let op = take_queued_op(
+            &mut ctx.accounts.timelock_queue,
+            &ctx.accounts.global_state,
+            &ctx.accounts.authority.key(),
+            ctx.accounts.admin_council.as_deref(),
+            id,
+            &payload,
+            clock.unix_timestamp,
+        )?;
+
+        let state = &mut ctx.accounts.global_state;
+        match op.op_type {
+            TIMELOCK_OP_PAUSE => state.pause_flags |= payload[0],
+            TIMELOCK_OP_UNPAUSE => state.pause_flags &= !payload[0],
+            TIMELOCK_OP_GUARDIAN => {
+                state.guardian = Pubkey::try_from_slice(&payload)
+                    .map_err(|_| ErrorCode::InvalidTimelockPayload)?;
+            }
+            TIMELOCK_OP_CONFIG => {
+                let params = ProtocolParams::try_from_slice(&payload)
+                    .map_err(|_| ErrorCode::InvalidTimelockPayload)?;
+                params.validate()?;
+                let config = &mut ctx.accounts.protocol_config;
+                config.params = params;
+                config.updated_at = clock.unix_timestamp;
+                emit!(ProtocolConfigUpdated {
+                    params,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+            TIMELOCK_OP_COUNCIL => {
+                let council = CouncilConfig::try_from_slice(&payload)
+                    .map_err(|_| ErrorCode::InvalidTimelockPayload)?;
+                council.validate()?;
+                let admin_council = ctx
+                    .accounts
+                    .admin_council
+                    .as_mut()
+                    .ok_or(ErrorCode::InvalidCouncil)?;
+                admin_council.members = council.members.clone();
+                admin_council.threshold = council.threshold;
+                ctx.accounts.timelock_queue.clear_approvals();
+                emit!(timelock::AdminCouncilUpdated {
+                    members: council.members,
+                    threshold: council.threshold,
+                });
+            }
+            TIMELOCK_OP_TREASURY_CONFIG => {
+                let config = treasury::TreasuryConfig::try_from_slice(&payload)
+                    .map_err(|_| ErrorCode::InvalidTimelockPayload)?;
+                config.validate()?;
+                let treasury = ctx
+                    .accounts
+                    .treasury
+                    .as_mut()
+                    .ok_or(ErrorCode::InvalidTreasuryConfig)?;
+                treasury.config = config.clone();
+                emit!(treasury::TreasuryConfigUpdated {
+                    config,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+            TIMELOCK_OP_VAULT_FLOORS => {
+                let floors = health::VaultFloors::try_from_slice(&payload)
+                    .map_err(|_| ErrorCode::InvalidTimelockPayload)?;
+                let vault_health = ctx
+                    .accounts
+                    .vault_health
+                    .as_mut()
+                    .ok_or(ErrorCode::InvalidTimelockPayload)?;
+                vault_health.referral_floor = floors.referral_floor;
+                vault_health.airdrop_floor = floors.airdrop_floor;
+                let (referral, airdrop) = (
+                    vault_health.balances[VAULT_KIND_REFERRAL as usize],
+                    vault_health.balances[VAULT_KIND_AIRDROP as usize],
+                );
+                vault_health.observe_referral(referral, clock.unix_timestamp);
+                vault_health.observe_airdrop(airdrop, clock.unix_timestamp);
+            }
+            // Executed by their own instructions, which carry the accounts
+            TIMELOCK_OP_VAULT
+            | TIMELOCK_OP_CLOSE_GLOBAL
+            | TIMELOCK_OP_CLOSE_POOL
+            | TIMELOCK_OP_TREASURY_WITHDRAW
+            | TIMELOCK_OP_AUTHORITY => return err!(ErrorCode::OperationMismatch),
+            _ => return err!(ErrorCode::InvalidTimelockPayload),
+        }
+
+        emit!(timelock::TimelockOpExecuted {
+            id,
+            op_type: op.op_type,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Drop a scheduled operation, pending or expired. Any single council
+    /// member may cancel.
+    pub fn cancel_op(ctx: Context<CancelOp>, id: u64) -> Result<()> {
+        admin_signer(
+            &ctx.accounts.global_state,
+            &ctx.accounts.authority.key(),
+            ctx.accounts.admin_council.as_deref(),
+        )?;
+        let op = ctx.accounts.timelock_queue.take(id)?;
+        emit!(timelock::TimelockOpCancelled {
+            id,
+            op_type: op.op_type,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Guardian emergency pause: sets `flags` immediately. Clearing them is
+    /// only possible through a timelocked TIMELOCK_OP_UNPAUSE.
+    pub fn guardian_pause(ctx: Context<GuardianPause>, flags: u8) -> Result<()> {
         require!(
-            ctx.accounts.global_state.pending_operation == TIMELOCK_OP_NONE,
-            ErrorCode::TimelockAlreadyPending
+            flags != 0 && flags & !PAUSE_ALL == 0,
+            ErrorCode::InvalidTimelockPayload
         );
+        let state = &mut ctx.accounts.global_state;
+        state.pause_flags |= flags;
 
-        let clock = Clock::get()?;
-        ctx.accounts.global_state.pending_operation = TIMELOCK_OP_UNPAUSE;
-        ctx.accounts.global_state.timelock_release =
-            clock.unix_timestamp + ctx.accounts.global_state.timelock_duration;
+        emit!(GuardianPaused {
+            guardian: ctx.accounts.guardian.key(),
+            flags,
+            pause_flags: state.pause_flags,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Propose a new authority for GlobalState, StakingState or AirdropState
+    /// (`target`); accept_authority completes it. Every handover waits out
+    /// timelock_duration. Proposing the current authority cancels.
+    ///
+    /// A target held by a single key is proposed by that key and the wait
+    /// starts here. A council-held target needs `op_id`: a ready
+    /// TIMELOCK_OP_AUTHORITY operation for this exact handover with
+    /// `threshold` approvals, which has already waited in the queue.
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Pubkey,
+        op_id: Option<u64>,
+    ) -> Result<()> {
+        require!(
+            new_authority != Pubkey::default(),
+            ErrorCode::InvalidAuthorityTarget
+        );
+        let target_info = ctx.accounts.target.to_account_info();
+        let target = AuthorityTarget::load(&target_info)?;
+        let current_authority = target.authority();
+        let proposer = ctx.accounts.proposer.key();
+
+        let now = Clock::get()?.unix_timestamp;
+        let eta = if proposer == current_authority {
+            now.checked_add(ctx.accounts.global_state.timelock_duration)
+                .ok_or(ErrorCode::MathOverflow)?
+        } else {
+            let council = ctx
+                .accounts
+                .admin_council
+                .as_deref()
+                .ok_or(ErrorCode::Unauthorized)?;
+            require!(
+                council.key() == current_authority && council.member_index(&proposer).is_some(),
+                ErrorCode::Unauthorized
+            );
+            let handover = AuthorityHandover {
+                target: target_info.key(),
+                new_authority,
+            };
+            let queue = ctx
+                .accounts
+                .timelock_queue
+                .as_deref_mut()
+                .ok_or(ErrorCode::OperationMismatch)?;
+            let op = queue.take_ready(
+                op_id.ok_or(ErrorCode::OperationMismatch)?,
+                &handover.try_to_vec()?,
+                now,
+            )?;
+            require!(
+                op.op_type == TIMELOCK_OP_AUTHORITY,
+                ErrorCode::OperationMismatch
+            );
+            require!(
+                council.approved(op.approvals),
+                ErrorCode::InsufficientApprovals
+            );
+            emit!(timelock::TimelockOpExecuted {
+                id: op.id,
+                op_type: op.op_type,
+                timestamp: now,
+            });
+            now
+        };
 
+        let proposal = &mut ctx.accounts.authority_proposal;
+        proposal.target = target_info.key();
+        proposal.proposed_authority = new_authority;
+        proposal.eta = eta;
+        proposal.bump = ctx.bumps.authority_proposal;
+
+        emit!(AuthorityProposed {
+            target: target_info.key(),
+            current_authority,
+            proposed_authority: new_authority,
+            eta,
+        });
         Ok(())
     }
 
-    /// Execute unpause after timelock expires
-    pub fn execute_unpause(ctx: Context<Unpause>) -> Result<()> {
+    /// Accept a proposed authority. Signed by the proposed key — or, when the
+    /// proposal names the AdminCouncil PDA, by a council member.
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let proposal = &ctx.accounts.authority_proposal;
+        require!(
+            Clock::get()?.unix_timestamp >= proposal.eta,
+            ErrorCode::TimelockNotExpired
+        );
         require!(
-            ctx.accounts.global_state.authority == ctx.accounts.authority.key(),
+            is_authority(
+                &proposal.proposed_authority,
+                &ctx.accounts.new_authority.key(),
+                ctx.accounts.admin_council.as_deref(),
+            ),
             ErrorCode::Unauthorized
         );
+
+        let target_info = ctx.accounts.target.to_account_info();
+        let mut target = AuthorityTarget::load(&target_info)?;
+        let previous_authority = target.authority();
+        target.set_authority(proposal.proposed_authority);
+        target.store(&target_info)?;
+
+        emit!(AuthorityAccepted {
+            target: target_info.key(),
+            previous_authority,
+            new_authority: proposal.proposed_authority,
+        });
+        Ok(())
+    }
+
+    /// Close GlobalState. Runs a ready TIMELOCK_OP_CLOSE_GLOBAL operation
+    /// (payload = recipient) and needs all three pools closed and every
+    /// registered vault (including AirdropState.vault) emptied first.
+    pub fn close_global_state(ctx: Context<CloseGlobalState>, op_id: u64) -> Result<()> {
+        let recipient = ctx.accounts.recipient.key();
+        let clock = Clock::get()?;
+        let op = take_queued_op(
+            &mut ctx.accounts.timelock_queue,
+            &ctx.accounts.global_state,
+            &ctx.accounts.authority.key(),
+            ctx.accounts.admin_council.as_deref(),
+            op_id,
+            &recipient.try_to_vec()?,
+            clock.unix_timestamp,
+        )?;
         require!(
-            ctx.accounts.global_state.pending_operation == TIMELOCK_OP_UNPAUSE,
-            ErrorCode::NoPendingOperation
+            op.op_type == TIMELOCK_OP_CLOSE_GLOBAL,
+            ErrorCode::OperationMismatch
         );
 
+        for pool in [
+            &ctx.accounts.pool_30min,
+            &ctx.accounts.pool_hourly,
+            &ctx.accounts.pool_daily,
+        ] {
+            require!(pool.data_is_empty(), ErrorCode::PoolsStillOpen);
+        }
+
+        // Only GlobalState can sign for these vaults; closing it with tokens
+        // left in any of them would strand the tokens for good
+        let accs = &ctx.accounts;
+        let mut balances = vec![
+            accs.platform_fee_vault.amount,
+            accs.airdrop_vault.amount,
+            accs.referral_vault.amount,
+            accs.reserve_vault.amount,
+            accs.prize_escrow_vault.amount,
+        ];
+        if !accs.airdrop_state.data_is_empty() {
+            let airdrop_state = airdrop::AirdropState::try_deserialize(
+                &mut &accs.airdrop_state.try_borrow_data()?[..],
+            )?;
+            let vault = accs
+                .profit_airdrop_vault
+                .as_ref()
+                .ok_or(ErrorCode::AirdropVaultMismatch)?;
+            require_keys_eq!(
+                vault.key(),
+                airdrop_state.vault,
+                ErrorCode::AirdropVaultMismatch
+            );
+            balances.push(vault.amount);
+        }
+        require!(balances.iter().all(|&b| b == 0), ErrorCode::VaultsNotEmpty);
+
+        emit!(GlobalStateClosed {
+            recipient,
+            timestamp: clock.unix_timestamp,
+        });
+        emit!(timelock::TimelockOpExecuted {
+            id: op_id,
+            op_type: op.op_type,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Close one PoolState. Runs a ready TIMELOCK_OP_CLOSE_POOL operation and
+    /// refuses while the pool holds tokens, has entries or a draw running,
+    /// owes earlier rounds anything, or has DrawResults not yet closed.
+    pub fn close_pool_state(ctx: Context<ClosePoolState>, pool_type: u8, op_id: u64) -> Result<()> {
+        let recipient = ctx.accounts.recipient.key();
         let clock = Clock::get()?;
+        let payload = ClosePool {
+            pool_type,
+            recipient,
+        }
+        .try_to_vec()?;
+        let op = take_queued_op(
+            &mut ctx.accounts.timelock_queue,
+            &ctx.accounts.global_state,
+            &ctx.accounts.authority.key(),
+            ctx.accounts.admin_council.as_deref(),
+            op_id,
+            &payload,
+            clock.unix_timestamp,
+        )?;
         require!(
-            clock.unix_timestamp >= ctx.accounts.global_state.timelock_release,
-            ErrorCode::TimelockNotExpired
+            op.op_type == TIMELOCK_OP_CLOSE_POOL,
+            ErrorCode::OperationMismatch
         );
 
-        ctx.accounts.global_state.is_paused = false;
-        ctx.accounts.global_state.pending_operation = TIMELOCK_OP_NONE;
-        ctx.accounts.global_state.timelock_release = 0;
+        let pool = &ctx.accounts.pool_state;
+        require!(
+            ctx.accounts.pool_vault.amount == 0
+                && pool.accounted_total()? == 0
+                && pool.regular_count == 0
+                && pool.free_count == 0
+                && !pool.draw_in_progress
+                && pool.open_draw_results == 0,
+            ErrorCode::PoolNotEmpty
+        );
 
+        emit!(PoolStateClosed {
+            pool_type,
+            round_number: pool.round_number,
+            recipient,
+            timestamp: clock.unix_timestamp,
+        });
+        emit!(timelock::TimelockOpExecuted {
+            id: op_id,
+            op_type: op.op_type,
+            timestamp: clock.unix_timestamp,
+        });
         Ok(())
     }
 
-    /// Cancel pending operation (only if not yet executed)
-    pub fn cancel_timelock(ctx: Context<CancelTimelock>) -> Result<()> {
+    /// Replace one registered vault. Runs a ready TIMELOCK_OP_VAULT
+    /// operation: creates the new vault PDA [b"vault", kind, nonce] (owned by
+    /// global_state), sweeps the old vault's balance into it and closes the
+    /// old vault (rent to payer).
+    ///
+    /// A pre-PDA platform vault not owned by global_state is neither swept nor
+    /// closed; its balance stays with its owner.
+    pub fn rotate_vault(
+        ctx: Context<RotateVault>,
+        op_id: u64,
+        rotation: VaultRotation,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let op = take_queued_op(
+            &mut ctx.accounts.timelock_queue,
+            &ctx.accounts.global_state,
+            &ctx.accounts.authority.key(),
+            ctx.accounts.admin_council.as_deref(),
+            op_id,
+            &rotation.try_to_vec()?,
+            clock.unix_timestamp,
+        )?;
         require!(
-            ctx.accounts.global_state.authority == ctx.accounts.authority.key(),
-            ErrorCode::Unauthorized
+            op.op_type == TIMELOCK_OP_VAULT,
+            ErrorCode::OperationMismatch
+        );
+
+        let state_key = ctx.accounts.global_state.key();
+        let old_vault = &ctx.accounts.old_vault;
+        require!(
+            old_vault.key()
+                == registered_vault(
+                    &ctx.accounts.global_state,
+                    ctx.accounts.airdrop_state.as_deref().map(|a| &**a),
+                    rotation.kind,
+                )?,
+            ErrorCode::VaultMismatch
         );
 
-        ctx.accounts.global_state.pending_operation = TIMELOCK_OP_NONE;
-        ctx.accounts.global_state.timelock_release = 0;
+        let owned = old_vault.owner == state_key;
+        let swept = if owned { old_vault.amount } else { 0 };
+        if owned {
+            let gs_bump = ctx.accounts.global_state.bump;
+            let gs_seeds: &[&[u8]] = &[b"global_state", &[gs_bump]];
+            let signer = &[gs_seeds];
+            if swept > 0 {
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: old_vault.to_account_info(),
+                            to: ctx.accounts.new_vault.to_account_info(),
+                            authority: ctx.accounts.global_state.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    swept,
+                )?;
+            }
+            token::close_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: old_vault.to_account_info(),
+                    destination: ctx.accounts.payer.to_account_info(),
+                    authority: ctx.accounts.global_state.to_account_info(),
+                },
+                signer,
+            ))?;
+        } else {
+            require!(
+                rotation.kind == VAULT_KIND_PLATFORM,
+                ErrorCode::VaultMismatch
+            );
+        }
+
+        let new_vault = ctx.accounts.new_vault.key();
+        match ctx.accounts.airdrop_state.as_deref_mut() {
+            Some(airdrop_state) if rotation.kind == VAULT_KIND_PROFIT_AIRDROP => {
+                airdrop_state.vault = new_vault;
+            }
+            _ => ctx
+                .accounts
+                .global_state
+                .set_vault(rotation.kind, new_vault)?,
+        }
 
+        emit!(VaultRotated {
+            kind: rotation.kind,
+            old_vault: ctx.accounts.old_vault.key(),
+            new_vault,
+            swept,
+            timestamp: clock.unix_timestamp,
+        });
+        emit!(timelock::TimelockOpExecuted {
+            id: op_id,
+            op_type: op.op_type,
+            timestamp: clock.unix_timestamp,
+        });
         Ok(())
     }
 
-    /// Close GlobalState account (admin only - for migration/reset)
-    pub fn close_global_state(ctx: Context<CloseGlobalState>) -> Result<()> {
+    /// Upgrade GlobalState from an older layout in place: realloc to
+    /// GLOBAL_STATE_SIZE (payer tops up rent) and rewrite it at
+    /// GLOBAL_STATE_VERSION. Authority (or council member) only.
+    pub fn migrate_global_state(ctx: Context<MigrateGlobalState>) -> Result<()> {
+        let info = ctx.accounts.global_state.to_account_info();
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::UnknownAccountLayout);
+        let (from_version, state) = migration::read_global_state(&info.try_borrow_data()?)?;
         require!(
-            ctx.accounts.global_state.authority == ctx.accounts.authority.key(),
+            is_authority(
+                &state.authority,
+                &ctx.accounts.authority.key(),
+                ctx.accounts.admin_council.as_deref(),
+            ),
             ErrorCode::Unauthorized
         );
 
-        let lamports = ctx.accounts.global_state.to_account_info().lamports();
-        **ctx
-            .accounts
-            .global_state
-            .to_account_info()
-            .try_borrow_mut_lamports()? -= lamports;
-        **ctx
-            .accounts
-            .recipient
-            .to_account_info()
-            .try_borrow_mut_lamports()? += lamports;
+        migration::store_resized(
+            &info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &state,
+            GLOBAL_STATE_SIZE,
+        )?;
 
+        emit!(migration::AccountMigrated {
+            account: info.key(),
+            from_version,
+            to_version: GLOBAL_STATE_VERSION,
+        });
         Ok(())
     }
 
-    /// Close PoolState account (admin only - for migration/reset)
-    pub fn close_pool_state(ctx: Context<ClosePoolState>) -> Result<()> {
+    /// Upgrade one PoolState from an older layout in place. Layouts without
+    /// round parameters take them from the current ProtocolConfig.
+    pub fn migrate_pool_state(ctx: Context<MigratePoolState>, pool_type: u8) -> Result<()> {
         require!(
-            ctx.accounts.global_state.authority == ctx.accounts.authority.key(),
+            is_authority(
+                &ctx.accounts.global_state.authority,
+                &ctx.accounts.authority.key(),
+                ctx.accounts.admin_council.as_deref(),
+            ),
             ErrorCode::Unauthorized
         );
+        let info = ctx.accounts.pool_state.to_account_info();
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::UnknownAccountLayout);
+        let params = ctx.accounts.protocol_config.params.for_pool(pool_type)?;
+        let (from_version, pool) = migration::read_pool_state(&info.try_borrow_data()?, params)?;
+        require!(pool.pool_type == pool_type, ErrorCode::InvalidPoolType);
+
+        migration::store_resized(
+            &info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &pool,
+            POOL_STATE_SIZE,
+        )?;
 
-        let lamports = ctx.accounts.pool_state.to_account_info().lamports();
-        **ctx
-            .accounts
-            .pool_state
-            .to_account_info()
-            .try_borrow_mut_lamports()? -= lamports;
-        **ctx
-            .accounts
-            .recipient
-            .to_account_info()
-            .try_borrow_mut_lamports()? += lamports;
+        emit!(migration::AccountMigrated {
+            account: info.key(),
+            from_version,
+            to_version: POOL_STATE_VERSION,
+        });
+        Ok(())
+    }
+
+    /// Upgrade the AirdropState from an older layout in place and register
+    /// its VAULT_KIND_PROFIT_AIRDROP vault, sweeping `legacy_vault` into it
+    /// if given. AirdropState.authority only.
+    pub fn migrate_airdrop_state(ctx: Context<MigrateAirdropState>) -> Result<()> {
+        let info = ctx.accounts.airdrop_state.to_account_info();
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::UnknownAccountLayout);
+        let (from_version, mut state) = migration::read_airdrop_state(&info.try_borrow_data()?)?;
+        require_keys_eq!(
+            state.authority,
+            ctx.accounts.authority.key(),
+            ErrorCode::Unauthorized
+        );
+        let new_vault = ctx.accounts.airdrop_vault.key();
+        state.vault = new_vault;
+
+        // Move funds out of a vault owned by the pre-registry [b"airdrop"] PDA
+        if let Some(legacy_vault) = &ctx.accounts.legacy_vault {
+            let legacy_authority = &ctx.accounts.legacy_airdrop_authority;
+            require!(
+                legacy_vault.owner == legacy_authority.key()
+                    && legacy_vault.mint == state.token_mint,
+                ErrorCode::AirdropVaultMismatch
+            );
+            let swept = legacy_vault.amount;
+            let seeds: &[&[u8]] = &[b"airdrop", &[ctx.bumps.legacy_airdrop_authority]];
+            let signer = &[seeds];
+            if swept > 0 {
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: legacy_vault.to_account_info(),
+                            to: ctx.accounts.airdrop_vault.to_account_info(),
+                            authority: legacy_authority.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    swept,
+                )?;
+            }
+            token::close_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: legacy_vault.to_account_info(),
+                    destination: ctx.accounts.payer.to_account_info(),
+                    authority: legacy_authority.to_account_info(),
+                },
+                signer,
+            ))?;
+            emit!(VaultRotated {
+                kind: VAULT_KIND_PROFIT_AIRDROP,
+                old_vault: legacy_vault.key(),
+                new_vault,
+                swept,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+
+        migration::store_resized(
+            &info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &state,
+            8 + airdrop::AirdropState::SIZE,
+        )?;
 
+        emit!(migration::AccountMigrated {
+            account: info.key(),
+            from_version,
+            to_version: airdrop::AIRDROP_STATE_VERSION,
+        });
         Ok(())
     }
 
@@ -632,12 +1909,13 @@ pub mod royalpot {
         pool_type: u8,
         initial_start_time: i64,
     ) -> Result<()> {
-        let pt = pool_type_from_u8(pool_type)?;
+        let params = ctx.accounts.protocol_config.params.for_pool(pool_type)?;
         let pool = &mut ctx.accounts.pool_state;
+        pool.version = POOL_STATE_VERSION;
         pool.pool_type = pool_type;
         pool.round_number = 1;
         pool.round_start_time = initial_start_time;
-        pool.round_end_time = initial_start_time + pt.duration();
+        pool.round_end_time = initial_start_time + params.duration;
         pool.total_deposited = 0;
         pool.free_bet_total = 0;
         pool.regular_count = 0;
@@ -645,7 +1923,12 @@ pub mod royalpot {
         pool.vault = ctx.accounts.pool_vault.key();
         pool.rollover = 0;
         pool.bump = ctx.bumps.pool_state;
-        pool._padding = [0u8; 7];
+        pool.draw_in_progress = false;
+        pool.open_draw_results = 0;
+        pool._padding = [0u8; 2];
+        pool.reserve_matched = 0;
+        pool.pending_payouts = 0;
+        pool.params = params;
         Ok(())
     }
 
@@ -657,26 +1940,26 @@ pub mod royalpot {
     ///
     /// remaining_accounts (optional):
     ///   [0] referrer's token account — pubkey is stored in UserDeposit.referrer.
-    ///       Referral (8%) is paid ONLY after a successful draw via claim_referral().
-    ///       No transfer happens at deposit time.
+    ///       Referral (8%) is credited ONLY after a successful draw via claim_referral().
+    ///       No transfer happens at deposit time. Ignored while
+    ///       VaultHealth.referral_disabled.
     pub fn deposit<'info>(
         ctx: Context<'_, '_, '_, 'info, Deposit<'info>>,
         amount: u64,
     ) -> Result<()> {
         require!(
-            !ctx.accounts.global_state.is_paused,
+            !ctx.accounts.global_state.paused(PAUSE_DEPOSITS),
             ErrorCode::ProtocolPaused
         );
 
         let clock = Clock::get()?;
         let pool = &mut ctx.accounts.pool_state;
-        let pt = pool_type_from_u8(pool.pool_type)?;
 
         require!(
-            clock.unix_timestamp < pool.round_end_time - LOCK_PERIOD,
+            clock.unix_timestamp < pool.round_end_time - pool.params.lock_period,
             ErrorCode::BettingClosed
         );
-        require!(amount >= pt.min_deposit(), ErrorCode::BelowMinimum);
+        require!(amount >= pool.params.min_deposit, ErrorCode::BelowMinimum);
 
         // Record deposit PDA (init fails if already exists → prevents double deposit)
         let dep = &mut ctx.accounts.user_deposit;
@@ -686,13 +1969,16 @@ pub mod royalpot {
         dep.amount = amount;
         // Store referrer's token account for deferred payout via claim_referral().
         // Referral is only paid after a successful draw — never on deposit or refund.
-        dep.referrer = if !ctx.remaining_accounts.is_empty() {
-            ctx.remaining_accounts[0].key()
-        } else {
-            Pubkey::default()
-        };
+        dep.referrer =
+            if !ctx.remaining_accounts.is_empty() && !ctx.accounts.vault_health.referral_disabled {
+                ctx.remaining_accounts[0].key()
+            } else {
+                Pubkey::default()
+            };
         dep.bump = ctx.bumps.user_deposit;
-        dep._padding = [0u8; 6];
+        dep.entry_index = pool.regular_count;
+        dep._padding = [0u8; 2];
+        dep.matched = 0;
 
         // ---------------------------------------------------
         // Record referee bonus eligibility (first deposit with referrer)
@@ -776,6 +2062,10 @@ pub mod royalpot {
             .checked_add(1)
             .ok_or(ErrorCode::MathOverflow)?;
 
+        let stats = &mut ctx.accounts.player_stats;
+        stats.open(ctx.accounts.user.key(), ctx.bumps.player_stats);
+        stats.record_entry(amount, false)?;
+
         // ---------------------------------------------------
         // Daily pool: 1:1 reserve matching
         // ---------------------------------------------------
@@ -803,6 +2093,11 @@ pub mod royalpot {
                     .total_deposited
                     .checked_add(matched)
                     .ok_or(ErrorCode::MathOverflow)?;
+                pool.reserve_matched = pool
+                    .reserve_matched
+                    .checked_add(matched)
+                    .ok_or(ErrorCode::MathOverflow)?;
+                ctx.accounts.user_deposit.matched = matched;
             }
         }
 
@@ -824,7 +2119,7 @@ pub mod royalpot {
         // Enforce daily-pool-only rule
         require!(pool_type == 2, ErrorCode::FreeBetDailyOnly);
         require!(
-            !ctx.accounts.global_state.is_paused,
+            !ctx.accounts.global_state.paused(PAUSE_DEPOSITS),
             ErrorCode::ProtocolPaused
         );
 
@@ -832,12 +2127,20 @@ pub mod royalpot {
         let pool = &mut ctx.accounts.pool_state;
 
         require!(
-            clock.unix_timestamp < pool.round_end_time - LOCK_PERIOD,
+            clock.unix_timestamp < pool.round_end_time - pool.params.lock_period,
             ErrorCode::BettingClosed
         );
 
         let claim = &mut ctx.accounts.airdrop_claim;
         require!(claim.free_bet_available, ErrorCode::NoFreeBetAvailable);
+        require!(
+            !ctx.accounts.vault_health.free_bet_disabled,
+            ErrorCode::FreeBetDisabled
+        );
+        require!(
+            ctx.accounts.airdrop_vault.amount >= FREE_BET_AMOUNT,
+            ErrorCode::FreeBetUnfunded
+        );
 
         let free_dep = &mut ctx.accounts.free_deposit;
         free_dep.user = ctx.accounts.user.key();
@@ -846,7 +2149,9 @@ pub mod royalpot {
         free_dep.amount = FREE_BET_AMOUNT;
         free_dep.referrer = Pubkey::default(); // free bets carry no referral
         free_dep.bump = ctx.bumps.free_deposit;
-        free_dep._padding = [0u8; 7];
+        free_dep.entry_index = pool.free_count;
+        free_dep.consumed_round = 0;
+        free_dep._padding = [0u8; 3];
 
         claim.free_bet_available = false;
 
@@ -866,6 +2171,10 @@ pub mod royalpot {
             ),
             FREE_BET_AMOUNT,
         )?;
+        ctx.accounts.vault_health.observe_airdrop(
+            ctx.accounts.airdrop_vault.amount - FREE_BET_AMOUNT,
+            clock.unix_timestamp,
+        );
 
         pool.free_bet_total = pool
             .free_bet_total
@@ -876,6 +2185,10 @@ pub mod royalpot {
             .checked_add(1)
             .ok_or(ErrorCode::MathOverflow)?;
 
+        let stats = &mut ctx.accounts.player_stats;
+        stats.open(ctx.accounts.user.key(), ctx.bumps.player_stats);
+        stats.record_entry(FREE_BET_AMOUNT, true)?;
+
         emit!(FreeBetActivated {
             pool_type: pool.pool_type,
             user: ctx.accounts.user.key(),
@@ -884,466 +2197,822 @@ pub mod royalpot {
         Ok(())
     }
 
-    /// Execute draw — call when total_count >= MIN_PARTICIPANTS.
+    /// Commit sha256(seed) for the current round of `pool_type`. Authority only.
     ///
-    /// remaining_accounts layout:
-    ///   [0 .. regular_count*2 - 1]:
-    ///     pairs (user_deposit_pda, user_token_account) for regular depositors
-    ///   [regular_count*2 .. (regular_count+free_count)*2 - 1]:
-    ///     pairs (free_deposit_pda, user_token_account) for free-bet holders
+    /// Must happen in the DRAW_COMMIT_LEAD before the deposit lock (round_end -
+    /// LOCK_PERIOD), while the participant set is still open, so the committer
+    /// cannot pick a seed that favours a known set of entries.
     ///
-    /// Referral payouts are NOT done here. After a successful draw, the cron
-    /// calls claim_referral() for each deposit that has a non-default referrer.
-    ///
-    /// Prize flow:
-    ///   3% burn · 2% platform · 5% rollover (stays in vault)
-    ///   Of remaining 90% prize pool (= 95% − 5% rollover):
-    ///     1st: 30% · 2nd: 10%×2 · 3rd: 5%×3 → prize_escrow_vault (vested 20 days)
-    ///     lucky: 2%×5 → immediate payment
-    ///     universal: 20% ÷ (total−11) → immediate payment to all non-prize-winners
-    pub fn execute_draw<'info>(
-        ctx: Context<'_, '_, '_, 'info, ExecuteDraw<'info>>,
-        draw_seed: [u8; 32],
+    /// The entropy slot is fixed here: the slot expected at round end, estimated
+    /// from SLOT_DURATION_MS. The short lead keeps that estimate close. The
+    /// committer also escrows a TPOT bond of DRAW_COMMIT_BOND_BP of the pot
+    /// (at least min_deposit), which is burned if the reveal is missed (see
+    /// close_draw_commit).
+    pub fn commit_draw_seed(
+        ctx: Context<CommitDrawSeed>,
+        pool_type: u8,
+        commitment: [u8; 32],
     ) -> Result<()> {
-        let clock = Clock::get()?;
         require!(
-            !ctx.accounts.global_state.is_paused,
+            !ctx.accounts.global_state.paused(PAUSE_DRAWS),
             ErrorCode::ProtocolPaused
         );
 
-        // Snapshot pool fields before mutable borrow
-        let pool_type = ctx.accounts.pool_state.pool_type;
-        let pool_bump = ctx.accounts.pool_state.bump;
-        let round_number = ctx.accounts.pool_state.round_number;
-        let round_end = ctx.accounts.pool_state.round_end_time;
-        let regular_count = ctx.accounts.pool_state.regular_count as usize;
-        let free_count = ctx.accounts.pool_state.free_count as usize;
-        let total_deposited = ctx.accounts.pool_state.total_deposited;
-        let free_bet_total = ctx.accounts.pool_state.free_bet_total;
-        let prev_rollover = ctx.accounts.pool_state.rollover;
-        let vault_key = ctx.accounts.pool_state.vault;
-
-        require!(
-            clock.unix_timestamp >= round_end,
-            ErrorCode::TooEarlyForDraw
-        );
+        let clock = Clock::get()?;
+        let pool = &ctx.accounts.pool_state;
+        let lock_start = pool.round_end_time - pool.params.lock_period;
         require!(
-            ctx.accounts.pool_vault.key() == vault_key,
-            ErrorCode::VaultMismatch
+            clock.unix_timestamp < lock_start
+                && clock.unix_timestamp >= lock_start - DRAW_COMMIT_LEAD,
+            ErrorCode::CommitWindowClosed
         );
+        let slots_to_end = (pool.round_end_time - clock.unix_timestamp) * 1_000 / SLOT_DURATION_MS;
+        let entropy_slot = clock
+            .slot
+            .checked_add(slots_to_end as u64)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let reveal_deadline_slot = entropy_slot
+            .checked_add(REVEAL_SLOTS)
+            .ok_or(ErrorCode::MathOverflow)?;
 
-        let total_count = regular_count + free_count;
+        let bond =
+            (pool.total_deposited as u128 * DRAW_COMMIT_BOND_BP as u128 / BASE as u128) as u64;
+        let bond = bond.max(pool.params.min_deposit);
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.committer_token.to_account_info(),
+                    to: ctx.accounts.draw_bond.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            bond,
+        )?;
+
+        let commit = &mut ctx.accounts.draw_commit;
+        commit.pool_type = pool_type;
+        commit.round_number = pool.round_number;
+        commit.committer = ctx.accounts.authority.key();
+        commit.commitment = commitment;
+        commit.commit_slot = clock.slot;
+        commit.entropy_slot = entropy_slot;
+        commit.reveal_deadline_slot = reveal_deadline_slot;
+        commit.bond = bond;
+        commit.forfeited = false;
+        commit.bump = ctx.bumps.draw_commit;
+
+        emit!(DrawSeedCommitted {
+            pool_type,
+            round_number: pool.round_number,
+            commitment,
+            commit_slot: clock.slot,
+            entropy_slot,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Reveal the committed seed and start the draw — call when
+    /// total_count >= MIN_PARTICIPANTS, by DrawCommit.reveal_deadline_slot.
+    ///
+    /// draw randomness = sha256(seed || hash of DrawCommit.entropy_slot); the
+    /// committer fixed both before entries closed and cannot know the slot hash
+    /// then, nor pick another one by revealing later. The hash must still be in
+    /// SlotHashes (~512 slots), hence the slot deadline. If the reveal never
+    /// comes, close_draw_commit burns the bond and opens execute_refund.
+    ///
+    /// Returns the bond to the committer, selects the winners and opens the
+    /// round's DrawProgress; prizes move only when crank_draw is called.
+    pub fn reveal_and_draw(ctx: Context<RevealAndDraw>, seed: [u8; 32]) -> Result<()> {
+        let clock = Clock::get()?;
         require!(
-            (total_count as u32) >= MIN_PARTICIPANTS,
-            ErrorCode::ShouldUseRefund
+            !ctx.accounts.global_state.paused(PAUSE_DRAWS),
+            ErrorCode::ProtocolPaused
         );
         require!(
-            ctx.remaining_accounts.len() == total_count * 2,
-            ErrorCode::ParticipantCountMismatch
+            clock.slot <= ctx.accounts.draw_commit.reveal_deadline_slot,
+            ErrorCode::RevealWindowExpired
         );
 
-        // -------------------------------------------------------
-        // Compute prize amounts
-        // -------------------------------------------------------
-        let total_pool = total_deposited
-            .checked_add(free_bet_total)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_add(prev_rollover)
-            .ok_or(ErrorCode::MathOverflow)?;
-
-        let burn_amount = total_pool
-            .checked_mul(BURN_RATE)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(BASE)
-            .ok_or(ErrorCode::MathOverflow)?;
-        let plat_amount = total_pool
-            .checked_mul(PLAT_RATE)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(BASE)
-            .ok_or(ErrorCode::MathOverflow)?;
-
-        // prize_pool = 95% of total_pool
-        let prize_pool = total_pool
-            .checked_sub(burn_amount)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_sub(plat_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        let slot_hash = randomness::slot_hash_at(
+            &ctx.accounts.slot_hashes,
+            ctx.accounts.draw_commit.entropy_slot,
+        )?;
+        let source = randomness::CommitRevealRandomness {
+            commitment: ctx.accounts.draw_commit.commitment,
+            commit_slot: ctx.accounts.draw_commit.commit_slot,
+            seed: Some(seed),
+            slot_hash,
+        };
+        let draw_seed = source.reveal(&clock)?;
 
-        // Rollover: 5% of prize_pool stays in vault
-        let rollover_amount = prize_pool
-            .checked_mul(ROLLOVER_BP)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(BASE)
-            .ok_or(ErrorCode::MathOverflow)?;
+        let pool_type = ctx.accounts.pool_state.pool_type;
+        let pool_bump = ctx.accounts.pool_state.bump;
+        let pool_seeds: &[&[u8]] = &[b"pool", &[pool_type], &[pool_bump]];
+        let signer = &[pool_seeds];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.draw_bond.to_account_info(),
+                    to: ctx.accounts.committer_token.to_account_info(),
+                    authority: ctx.accounts.pool_state.to_account_info(),
+                },
+                signer,
+            ),
+            ctx.accounts.draw_bond.amount,
+        )?;
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.draw_bond.to_account_info(),
+                destination: ctx.accounts.caller.to_account_info(),
+                authority: ctx.accounts.pool_state.to_account_info(),
+            },
+            signer,
+        ))?;
+
+        let accs = DrawAccounts {
+            payer: ctx.accounts.caller.key(),
+            pool_state: &mut ctx.accounts.pool_state,
+            draw_result: &mut ctx.accounts.draw_result,
+            draw_result_bump: ctx.bumps.draw_result,
+            draw_progress: &mut ctx.accounts.draw_progress,
+            draw_progress_bump: ctx.bumps.draw_progress,
+        };
+        begin_draw(accs, draw_seed)
+    }
 
-        // distributable = prize_pool - rollover
-        let distributable = prize_pool
-            .checked_sub(rollover_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+    /// Settle a DrawCommit that was never revealed. Permissionless.
+    ///
+    /// If its round reached min_participants and reveal_deadline_slot has
+    /// passed, the bond is burned — it goes neither to the committer nor to
+    /// anything the authority controls, so withholding the seed to force a
+    /// refund costs the committer the whole bond. The record stays, marked
+    /// forfeited, as execute_refund's proof; close it again once the round has
+    /// moved on. Otherwise (round too small, or already settled another way)
+    /// bond and rent go back to the committer.
+    pub fn close_draw_commit(
+        ctx: Context<CloseDrawCommit>,
+        pool_type: u8,
+        round_number: u64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let pool = &ctx.accounts.pool_state;
+        let forfeit = if round_number == pool.round_number {
+            require!(
+                clock.unix_timestamp >= pool.round_end_time,
+                ErrorCode::TooEarlyForDraw
+            );
+            require!(!pool.draw_in_progress, ErrorCode::DrawInProgress);
+            require!(
+                !ctx.accounts.draw_commit.forfeited,
+                ErrorCode::DrawCommitOutstanding
+            );
+            let full = pool.regular_count + pool.free_count >= pool.params.min_participants;
+            if full {
+                require!(
+                    clock.slot > ctx.accounts.draw_commit.reveal_deadline_slot,
+                    ErrorCode::RevealWindowOpen
+                );
+            }
+            full
+        } else {
+            false
+        };
 
-        let prize_1st = distributable
-            .checked_mul(PRIZE_1ST_BP)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(BASE)
-            .ok_or(ErrorCode::MathOverflow)?;
-        let prize_2nd_each = distributable
-            .checked_mul(PRIZE_2ND_EACH_BP)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(BASE)
-            .ok_or(ErrorCode::MathOverflow)?;
-        let prize_3rd_each = distributable
-            .checked_mul(PRIZE_3RD_EACH_BP)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(BASE)
-            .ok_or(ErrorCode::MathOverflow)?;
-        let prize_lucky_each = distributable
-            .checked_mul(PRIZE_LUCKY_EACH_BP)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(BASE)
-            .ok_or(ErrorCode::MathOverflow)?;
-        let prize_universal_total = distributable
-            .checked_mul(PRIZE_UNIVERSAL_BP)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(BASE)
-            .ok_or(ErrorCode::MathOverflow)?;
+        let pool_bump = pool.bump;
+        let pool_seeds: &[&[u8]] = &[b"pool", &[pool_type], &[pool_bump]];
+        let signer = &[pool_seeds];
+        let mut bond = 0;
+        if !ctx.accounts.draw_commit.forfeited {
+            let draw_bond = ctx
+                .accounts
+                .draw_bond
+                .as_ref()
+                .ok_or(ErrorCode::VaultMismatch)?;
+            bond = draw_bond.amount;
+            if forfeit {
+                token::burn(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Burn {
+                            mint: ctx.accounts.token_mint.to_account_info(),
+                            from: draw_bond.to_account_info(),
+                            authority: ctx.accounts.pool_state.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    bond,
+                )?;
+            } else {
+                let committer_token = ctx
+                    .accounts
+                    .committer_token
+                    .as_ref()
+                    .ok_or(ErrorCode::PayoutAccountMismatch)?;
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: draw_bond.to_account_info(),
+                            to: committer_token.to_account_info(),
+                            authority: ctx.accounts.pool_state.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    bond,
+                )?;
+            }
+            token::close_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: draw_bond.to_account_info(),
+                    destination: ctx.accounts.committer.to_account_info(),
+                    authority: ctx.accounts.pool_state.to_account_info(),
+                },
+                signer,
+            ))?;
+        }
 
-        // universal_count = total participants - 11 prize winners
-        let universal_count = total_count.saturating_sub(11);
-        let prize_universal_each = if universal_count > 0 {
-            prize_universal_total
-                .checked_div(universal_count as u64)
-                .ok_or(ErrorCode::MathOverflow)?
+        if forfeit {
+            ctx.accounts.draw_commit.forfeited = true;
         } else {
-            0u64
-        };
+            ctx.accounts
+                .draw_commit
+                .close(ctx.accounts.committer.to_account_info())?;
+        }
 
-        // Total going to prize_escrow = 1st + 2×2nd + 3×3rd
-        let top_prize_total = prize_1st
-            .checked_add(
-                prize_2nd_each
-                    .checked_mul(2)
-                    .ok_or(ErrorCode::MathOverflow)?,
-            )
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_add(
-                prize_3rd_each
-                    .checked_mul(3)
-                    .ok_or(ErrorCode::MathOverflow)?,
-            )
-            .ok_or(ErrorCode::MathOverflow)?;
+        emit!(DrawCommitClosed {
+            pool_type,
+            round_number,
+            committer: ctx.accounts.committer.key(),
+            forfeited: forfeit,
+            bond,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
 
-        // -------------------------------------------------------
-        // Select 11 distinct winners
-        // -------------------------------------------------------
-        let winner_indices = pick_winner_indices(draw_seed, total_count);
-        // Build is_winner mask
-        let mut is_winner = vec![false; total_count];
-        for &w in &winner_indices {
-            is_winner[w] = true;
+    // ----------------------------------------------------------
+    // VRF draw (randomness::RandomnessSource — Switchboard On-Demand,
+    // or programs/mock-oracle when built with `--features mock-oracle`)
+    // ----------------------------------------------------------
+
+    /// Register the randomness account used by VRF draws. Authority only.
+    pub fn initialize_vrf(ctx: Context<InitializeVrf>) -> Result<()> {
+        let vrf_state = &mut ctx.accounts.vrf_state;
+        vrf_state.authority = ctx.accounts.authority.key();
+        vrf_state.vrf = ctx.accounts.vrf.key();
+        vrf_state.pending_draw = None;
+        vrf_state.bump = ctx.bumps.vrf_state;
+        Ok(())
+    }
+
+    /// Bind the finished round of `pool_type` to a freshly committed VRF value.
+    ///
+    /// Cron (VrfState.authority) or GlobalState.authority — the cron commits the
+    /// randomness account in slot N-1 and calls this in slot N, so the value is
+    /// still unknown when it is bound.
+    /// Only one request may be pending at a time; a request older than
+    /// VRF_TIMEOUT_SECONDS may be replaced by a new one. Each replacement is a
+    /// fresh roll, so a round gets at most MAX_VRF_REQUESTS, all within
+    /// REVEAL_WINDOW; once the last one times out the round is refunded.
+    pub fn request_vrf(ctx: Context<RequestVrf>, pool_type: u8) -> Result<()> {
+        require!(
+            !ctx.accounts.global_state.paused(PAUSE_DRAWS),
+            ErrorCode::ProtocolPaused
+        );
+        let clock = Clock::get()?;
+        let pool = &ctx.accounts.pool_state;
+        require!(
+            clock.unix_timestamp >= pool.round_end_time,
+            ErrorCode::TooEarlyForDraw
+        );
+        require!(
+            clock.unix_timestamp <= pool.round_end_time + REVEAL_WINDOW,
+            ErrorCode::RevealWindowExpired
+        );
+        require!(
+            pool.regular_count + pool.free_count >= pool.params.min_participants,
+            ErrorCode::ShouldUseRefund
+        );
+
+        let vrf_state = &mut ctx.accounts.vrf_state;
+        if let Some(pending) = &vrf_state.pending_draw {
+            require!(
+                clock.unix_timestamp - pending.request_timestamp > VRF_TIMEOUT_SECONDS,
+                VrfErrorCode::VrfRequestPending
+            );
         }
+        let request_count = vrf_state.requests_for(pool_type, pool.round_number) + 1;
+        require!(
+            request_count <= MAX_VRF_REQUESTS,
+            VrfErrorCode::VrfRequestsExhausted
+        );
+
+        let source = randomness::load_vrf_source(&ctx.accounts.vrf)?;
+        let commit_slot = source.commit_slot();
+        require!(
+            commit_slot == clock.slot.saturating_sub(1) && !source.is_revealed(),
+            VrfErrorCode::StaleRandomness
+        );
+
+        // request_slot records the commit slot the reveal must match
+        vrf_state.pending_draw = Some(PendingDraw {
+            pool_type,
+            round_number: pool.round_number,
+            request_slot: commit_slot,
+            request_timestamp: clock.unix_timestamp,
+        });
+        vrf_state.request_pool = pool_type;
+        vrf_state.request_round = pool.round_number;
+        vrf_state.request_count = request_count;
+
+        emit!(VrfRequested {
+            pool_type,
+            round_number: pool.round_number,
+            vrf_account: ctx.accounts.vrf.key(),
+            request_slot: commit_slot,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Start the draw using the revealed VRF value instead of a caller-chosen seed.
+    ///
+    /// The reveal instruction must be in the same transaction (reveal_slot == now),
+    /// so nobody can see the value first and decide not to draw.
+    /// Payouts follow via crank_draw, as for reveal_and_draw.
+    pub fn execute_draw_vrf(ctx: Context<ExecuteDrawVrf>) -> Result<()> {
+        let clock = Clock::get()?;
+        require!(
+            !ctx.accounts.global_state.paused(PAUSE_DRAWS),
+            ErrorCode::ProtocolPaused
+        );
+
+        let pending = ctx
+            .accounts
+            .vrf_state
+            .pending_draw
+            .clone()
+            .ok_or(VrfErrorCode::NoPendingVrfRequest)?;
+        require!(
+            pending.pool_type == ctx.accounts.pool_state.pool_type
+                && pending.round_number == ctx.accounts.pool_state.round_number,
+            VrfErrorCode::PendingDrawMismatch
+        );
+        require!(
+            clock.unix_timestamp - pending.request_timestamp <= VRF_TIMEOUT_SECONDS,
+            VrfErrorCode::VrfRequestTimeout
+        );
+        require!(
+            clock.unix_timestamp <= ctx.accounts.pool_state.round_end_time + REVEAL_WINDOW,
+            ErrorCode::RevealWindowExpired
+        );
+
+        let source = randomness::load_vrf_source(&ctx.accounts.vrf)?;
+        require!(
+            source.commit_slot() == pending.request_slot,
+            VrfErrorCode::StaleRandomness
+        );
+        let value = source.reveal(&clock)?;
+
+        ctx.accounts.vrf_state.pending_draw = None;
+
+        emit!(VrfFulfilled {
+            pool_type: pending.pool_type,
+            round_number: pending.round_number,
+            randomness: value,
+            timestamp: clock.unix_timestamp,
+        });
+
+        let accs = DrawAccounts {
+            payer: ctx.accounts.caller.key(),
+            pool_state: &mut ctx.accounts.pool_state,
+            draw_result: &mut ctx.accounts.draw_result,
+            draw_result_bump: ctx.bumps.draw_result,
+            draw_progress: &mut ctx.accounts.draw_progress,
+            draw_progress_bump: ctx.bumps.draw_progress,
+        };
+        begin_draw(accs, value)
+    }
+
+    /// Advance a started draw. Permissionless — the cron calls this repeatedly
+    /// until DrawExecuted is emitted.
+    ///
+    /// Each call runs every step it can reach:
+    ///   SETTLE   — 3% burn, 2% platform fee, top prizes → prize_escrow_vault
+    ///   RECORD   — verifies the page of deposit PDAs passed in remaining_accounts,
+    ///              records top/lucky winner pubkeys and consumes free-bet entries
+    ///   FINALIZE — starts vesting and claims, emits DrawExecuted, advances the
+    ///              pool round and closes DrawProgress (rent → caller)
+    ///
+    /// remaining_accounts: deposit PDAs for participant indices cursor, cursor+1, …
+    /// — regular deposits by entry_index, then free-bet entries. May be empty,
+    /// e.g. to run SETTLE alone.
+    ///
+    /// No lucky/universal tokens move here: they stay in pool_vault until each
+    /// participant's claim_round_prize.
+    ///
+    /// Referral payouts are NOT done here. After a successful draw, the cron
+    /// calls claim_referral() for each deposit that has a non-default referrer.
+    ///
+    /// Prize flow:
+    ///   3% burn · 2% platform · 5% rollover (stays in vault, with any unallocated remainder)
+    ///   Of remaining 90% prize pool (= 95% − 5% rollover):
+    ///     1st: 30% · 2nd: 10%×2 · 3rd: 5%×3 → prize_escrow_vault (vested 20 days)
+    ///     lucky: 2%×5 → claim_round_prize
+    ///     universal: 20% ÷ (total−11) → claim_round_prize, all non-prize-winners
+    pub fn crank_draw<'info>(ctx: Context<'_, '_, '_, 'info, CrankDraw<'info>>) -> Result<()> {
+        require!(
+            !ctx.accounts.global_state.paused(PAUSE_DRAWS),
+            ErrorCode::ProtocolPaused
+        );
+        let clock = Clock::get()?;
 
+        let pool_type = ctx.accounts.pool_state.pool_type;
+        let pool_bump = ctx.accounts.pool_state.bump;
         let pool_seeds: &[&[u8]] = &[b"pool", &[pool_type], &[pool_bump]];
         let pool_signer = &[pool_seeds];
 
-        // -------------------------------------------------------
-        // 1. Burn 3%
-        // -------------------------------------------------------
-        token::burn(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Burn {
-                    mint: ctx.accounts.token_mint.to_account_info(),
-                    from: ctx.accounts.pool_vault.to_account_info(),
-                    authority: ctx.accounts.pool_state.to_account_info(),
-                },
-                pool_signer,
-            ),
-            burn_amount,
-        )?;
+        let progress = &mut ctx.accounts.draw_progress;
 
         // -------------------------------------------------------
-        // 2. Platform fee 2%
+        // SETTLE: burn, platform fee, top prizes to escrow
         // -------------------------------------------------------
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.pool_vault.to_account_info(),
-                    to: ctx.accounts.platform_vault.to_account_info(),
-                    authority: ctx.accounts.pool_state.to_account_info(),
-                },
-                pool_signer,
-            ),
-            plat_amount,
-        )?;
+        if progress.phase == DRAW_PHASE_SETTLE {
+            token::burn(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: ctx.accounts.token_mint.to_account_info(),
+                        from: ctx.accounts.pool_vault.to_account_info(),
+                        authority: ctx.accounts.pool_state.to_account_info(),
+                    },
+                    pool_signer,
+                ),
+                progress.burn_amount,
+            )?;
 
-        // -------------------------------------------------------
-        // 3. Transfer ALL top prizes to prize_escrow_vault (vested 20 days)
-        // -------------------------------------------------------
-        if top_prize_total > 0 {
             token::transfer(
                 CpiContext::new_with_signer(
                     ctx.accounts.token_program.to_account_info(),
                     Transfer {
                         from: ctx.accounts.pool_vault.to_account_info(),
-                        to: ctx.accounts.prize_escrow_vault.to_account_info(),
+                        to: ctx.accounts.platform_vault.to_account_info(),
                         authority: ctx.accounts.pool_state.to_account_info(),
                     },
                     pool_signer,
                 ),
-                top_prize_total,
+                progress.platform_amount,
             )?;
-        }
 
-        // -------------------------------------------------------
-        // 4. Immediate payouts: lucky winners (indices 6..10)
-        // -------------------------------------------------------
-        let mut lucky_winners = [Pubkey::default(); 5];
-        for i in 0..5 {
-            let idx = winner_indices[6 + i];
-            let tok_acc = &ctx.remaining_accounts[idx * 2 + 1];
-            if prize_lucky_each > 0 {
+            if progress.top_prize_total > 0 {
                 token::transfer(
                     CpiContext::new_with_signer(
                         ctx.accounts.token_program.to_account_info(),
                         Transfer {
                             from: ctx.accounts.pool_vault.to_account_info(),
-                            to: tok_acc.to_account_info(),
+                            to: ctx.accounts.prize_escrow_vault.to_account_info(),
                             authority: ctx.accounts.pool_state.to_account_info(),
                         },
                         pool_signer,
                     ),
-                    prize_lucky_each,
+                    progress.top_prize_total,
                 )?;
             }
-            // Read lucky winner pubkey (from deposit or free-deposit PDA)
-            lucky_winners[i] = read_participant_pubkey(
-                &ctx.remaining_accounts[idx * 2],
-                idx < regular_count,
-                pool_type,
-                round_number,
-            )?;
-        }
 
-        // -------------------------------------------------------
-        // 5. Immediate payouts: universal winners (all non-prize-winners)
-        // -------------------------------------------------------
-        for idx in 0..total_count {
-            if !is_winner[idx] && prize_universal_each > 0 {
-                let tok_acc = &ctx.remaining_accounts[idx * 2 + 1];
-                token::transfer(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.token_program.to_account_info(),
-                        Transfer {
-                            from: ctx.accounts.pool_vault.to_account_info(),
-                            to: tok_acc.to_account_info(),
-                            authority: ctx.accounts.pool_state.to_account_info(),
-                        },
-                        pool_signer,
-                    ),
-                    prize_universal_each,
-                )?;
-            }
+            // The round's entries are now either gone (burn/fee/escrow), owed to
+            // claim_round_prize, or the next round's rollover.
+            let pool = &mut ctx.accounts.pool_state;
+            pool.pending_payouts = pool
+                .pending_payouts
+                .checked_add(progress.round_prize_total)
+                .ok_or(ErrorCode::MathOverflow)?;
+            pool.rollover = progress.rollover_amount;
+            pool.total_deposited = 0;
+            pool.free_bet_total = 0;
+            pool.reserve_matched = 0;
+
+            progress.phase = DRAW_PHASE_RECORD;
         }
 
         // -------------------------------------------------------
-        // 6. Build DrawResult (top-6 winners + amounts)
+        // RECORD: one page of participants starting at cursor
         // -------------------------------------------------------
-        let top_amounts: [u64; 6] = [
-            prize_1st,
-            prize_2nd_each,
-            prize_2nd_each,
-            prize_3rd_each,
-            prize_3rd_each,
-            prize_3rd_each,
-        ];
-        let mut top_winners = [Pubkey::default(); 6];
-        for i in 0..6 {
-            let idx = winner_indices[i];
-            top_winners[i] = read_participant_pubkey(
-                &ctx.remaining_accounts[idx * 2],
-                idx < regular_count,
+        let draw_result = &mut ctx.accounts.draw_result;
+        if progress.phase == DRAW_PHASE_RECORD {
+            let remaining = ctx.remaining_accounts;
+            let page_end = progress
+                .cursor
+                .checked_add(remaining.len() as u32)
+                .ok_or(ErrorCode::MathOverflow)?;
+            require!(
+                page_end <= draw_result.participant_count,
+                ErrorCode::ParticipantCountMismatch
+            );
+
+            let set = ParticipantSet {
                 pool_type,
-                round_number,
-            )?;
-        }
+                round_number: progress.round_number,
+                regular_count: draw_result.regular_count,
+                free_count: draw_result.participant_count - draw_result.regular_count,
+            };
+            let users = set.verify_page(remaining, progress.cursor)?;
+
+            for (k, (dep_acc, user)) in remaining.iter().zip(users).enumerate() {
+                let index = progress.cursor + k as u32;
+
+                match draw_result.winner_indices.iter().position(|&w| w == index) {
+                    // 1st / 2nd / 3rd — already in escrow, vested via claim_prize_vesting
+                    Some(slot) if slot < 6 => draw_result.top_winners[slot] = user,
+                    Some(slot) => progress.lucky_winners[slot - 6] = user,
+                    None => {}
+                }
 
-        let draw_result = &mut ctx.accounts.draw_result;
-        draw_result.pool_type = pool_type;
-        draw_result.round_number = round_number;
-        draw_result.top_winners = top_winners;
-        draw_result.top_amounts = top_amounts;
-        draw_result.top_claimed = [0u64; 6];
-        draw_result.draw_timestamp = clock.unix_timestamp;
-        draw_result.bump = ctx.bumps.draw_result;
+                // Free-bet entry consumed by this round
+                if index >= draw_result.regular_count {
+                    let mut dep =
+                        FreeDeposit::try_deserialize(&mut dep_acc.data.borrow().as_ref())?;
+                    dep.is_active = false;
+                    dep.consumed_round = progress.round_number;
+                    dep.try_serialize(&mut &mut dep_acc.try_borrow_mut_data()?[..])?;
+                }
+            }
 
-        // -------------------------------------------------------
-        // 7. Mark all free-bet accounts as consumed (is_active = false)
-        //    is_active byte offset in FreeDeposit: disc(8)+user(32)+pool_type(1) = offset 41
-        // -------------------------------------------------------
-        for i in 0..free_count {
-            let free_acc = &ctx.remaining_accounts[(regular_count + i) * 2];
-            let mut data = free_acc.try_borrow_mut_data()?;
-            data[41] = 0u8;
+            progress.cursor = page_end;
+            if progress.cursor == draw_result.participant_count {
+                progress.phase = DRAW_PHASE_FINALIZE;
+            }
         }
 
         // -------------------------------------------------------
-        // 8. Emit event
+        // FINALIZE: start vesting and claims, emit, advance to next round
         // -------------------------------------------------------
-        emit!(DrawExecuted {
-            pool_type,
-            round_number,
-            total_pool,
-            participant_count: total_count as u32,
-            top_winners,
-            top_amounts,
-            lucky_winners,
-            lucky_amount_each: prize_lucky_each,
-            universal_count: universal_count as u32,
-            universal_amount_each: prize_universal_each,
-            burn_amount,
-            platform_amount: plat_amount,
-            rollover_amount,
-            draw_seed,
-            timestamp: clock.unix_timestamp,
-        });
+        if progress.phase == DRAW_PHASE_FINALIZE {
+            draw_result.draw_timestamp = clock.unix_timestamp;
 
-        // -------------------------------------------------------
-        // 9. Advance to next round
-        // -------------------------------------------------------
-        let pt = pool_type_from_u8(pool_type)?;
-        let pool = &mut ctx.accounts.pool_state;
-        pool.round_number = pool
-            .round_number
-            .checked_add(1)
-            .ok_or(ErrorCode::MathOverflow)?;
-        pool.round_start_time = round_end;
-        pool.round_end_time = round_end + pt.duration();
-        pool.total_deposited = 0;
-        pool.free_bet_total = 0;
-        pool.regular_count = 0;
-        pool.free_count = 0;
-        pool.rollover = rollover_amount;
+            let participant_count = draw_result.participant_count;
+            emit!(DrawExecuted {
+                pool_type,
+                round_number: progress.round_number,
+                total_pool: progress.total_pool,
+                participant_count,
+                top_winners: draw_result.top_winners,
+                top_amounts: draw_result.top_amounts,
+                lucky_winners: progress.lucky_winners,
+                lucky_amount_each: draw_result.lucky_amount_each,
+                universal_count: participant_count - 11,
+                universal_amount_each: draw_result.universal_amount_each,
+                burn_amount: progress.burn_amount,
+                platform_amount: progress.platform_amount,
+                rollover_amount: progress.rollover_amount,
+                draw_seed: progress.draw_seed,
+                timestamp: clock.unix_timestamp,
+            });
+
+            // Totals and rollover were already moved over in SETTLE.
+            // The next round runs with the current ProtocolConfig.
+            let params = ctx.accounts.protocol_config.params.for_pool(pool_type)?;
+            let pool = &mut ctx.accounts.pool_state;
+            let round_end = pool.round_end_time;
+            pool.round_number = pool
+                .round_number
+                .checked_add(1)
+                .ok_or(ErrorCode::MathOverflow)?;
+            pool.round_start_time = round_end;
+            pool.round_end_time = round_end + params.duration;
+            pool.params = params;
+            pool.regular_count = 0;
+            pool.free_count = 0;
+            pool.draw_in_progress = false;
+
+            ctx.accounts
+                .draw_progress
+                .close(ctx.accounts.caller.to_account_info())?;
+        }
 
         Ok(())
     }
 
-    /// Execute refund — call when total_count < MIN_PARTICIPANTS.
+    /// Mark the round refunded — call when total_count < MIN_PARTICIPANTS, or as
+    /// the fallback when a full round was not drawn: its seed reveal was missed
+    /// (DrawCommit forfeited), or nothing drew it within REVEAL_WINDOW of round end.
     ///
-    /// remaining_accounts: (user_deposit_pda, user_token_account) × regular_count
-    /// Free-bet entries carry over automatically (no accounts needed).
-    pub fn execute_refund<'info>(
-        ctx: Context<'_, '_, '_, 'info, ExecuteRefund<'info>>,
-    ) -> Result<()> {
+    /// Creates the round's RoundRefund record and advances the pool. Daily-pool
+    /// reserve matching goes straight back to reserve_vault; each depositor's
+    /// own amount stays in pool_vault until claim_refund, so one closed or
+    /// missing account cannot block anyone else's refund.
+    /// Free-bet entries carry over automatically.
+    pub fn execute_refund(ctx: Context<ExecuteRefund>) -> Result<()> {
         let clock = Clock::get()?;
 
         let pool_type = ctx.accounts.pool_state.pool_type;
-        let pool_bump = ctx.accounts.pool_state.bump;
         let round_number = ctx.accounts.pool_state.round_number;
         let round_end = ctx.accounts.pool_state.round_end_time;
-        let regular_count = ctx.accounts.pool_state.regular_count as usize;
-        let free_count = ctx.accounts.pool_state.free_count as usize;
+        let regular_count = ctx.accounts.pool_state.regular_count;
+        let free_count = ctx.accounts.pool_state.free_count;
         let total_deposited = ctx.accounts.pool_state.total_deposited;
-        let vault_key = ctx.accounts.pool_state.vault;
+        let reserve_matched = ctx.accounts.pool_state.reserve_matched;
+        let min_participants = ctx.accounts.pool_state.params.min_participants;
 
         require!(
             clock.unix_timestamp >= round_end,
             ErrorCode::TooEarlyForDraw
         );
         require!(
-            ctx.accounts.pool_vault.key() == vault_key,
-            ErrorCode::VaultMismatch
+            !ctx.accounts.pool_state.draw_in_progress,
+            ErrorCode::DrawInProgress
         );
 
         let total_count = regular_count + free_count;
+        // A missed reveal is settled (bond burned) before the round is refunded
+        let commit_forfeited = if ctx.accounts.draw_commit.data_is_empty() {
+            false
+        } else {
+            let commit =
+                DrawCommit::try_deserialize(&mut &ctx.accounts.draw_commit.try_borrow_data()?[..])?;
+            require!(
+                total_count < min_participants || commit.forfeited,
+                ErrorCode::DrawCommitOutstanding
+            );
+            commit.forfeited
+        };
+        let vrf_exhausted =
+            ctx.accounts.vrf_state.as_ref().is_some_and(|v| {
+                v.requests_exhausted(pool_type, round_number, clock.unix_timestamp)
+            });
         require!(
-            (total_count as u32) < MIN_PARTICIPANTS,
+            total_count < min_participants
+                || clock.unix_timestamp > round_end + REVEAL_WINDOW
+                || vrf_exhausted
+                || commit_forfeited,
             ErrorCode::ShouldUseDraw
         );
-        require!(
-            ctx.remaining_accounts.len() == regular_count * 2,
-            ErrorCode::ParticipantCountMismatch
-        );
-
-        let pool_seeds: &[&[u8]] = &[b"pool", &[pool_type], &[pool_bump]];
-        let signer = &[pool_seeds];
-
-        for i in 0..regular_count {
-            let dep_acc = &ctx.remaining_accounts[i * 2];
-            let user_tok_acc = &ctx.remaining_accounts[i * 2 + 1];
-
-            let dep_data = UserDeposit::try_deserialize(&mut dep_acc.data.borrow().as_ref())?;
-            require!(
-                dep_data.pool_type == pool_type,
-                ErrorCode::InvalidParticipant
-            );
-            require!(
-                dep_data.round_number == round_number,
-                ErrorCode::WrongRoundNumber
-            );
 
+        // Reserve matching was never the depositors' money — send it back
+        if reserve_matched > 0 {
+            let pool_bump = ctx.accounts.pool_state.bump;
+            let pool_seeds: &[&[u8]] = &[b"pool", &[pool_type], &[pool_bump]];
+            let signer = &[pool_seeds];
             token::transfer(
                 CpiContext::new_with_signer(
                     ctx.accounts.token_program.to_account_info(),
                     Transfer {
                         from: ctx.accounts.pool_vault.to_account_info(),
-                        to: user_tok_acc.to_account_info(),
+                        to: ctx.accounts.reserve_vault.to_account_info(),
                         authority: ctx.accounts.pool_state.to_account_info(),
                     },
                     signer,
                 ),
-                dep_data.amount,
+                reserve_matched,
             )?;
         }
+        let user_owed = total_deposited
+            .checked_sub(reserve_matched)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let refund = &mut ctx.accounts.round_refund;
+        refund.pool_type = pool_type;
+        refund.round_number = round_number;
+        refund.regular_count = regular_count;
+        refund.claimed_count = 0;
+        refund.total_deposited = total_deposited;
+        refund.claimed_amount = 0;
+        refund.refunded_at = clock.unix_timestamp;
+        refund.bump = ctx.bumps.round_refund;
+        refund.reserve_returned = reserve_matched;
 
         emit!(RoundRefunded {
             pool_type,
             round_number,
-            regular_refunded: regular_count as u32,
-            free_carried_over: free_count as u32,
-            total_refunded: total_deposited,
+            regular_refunded: regular_count,
+            free_carried_over: free_count,
+            total_refunded: user_owed,
+            reserve_returned: reserve_matched,
             timestamp: clock.unix_timestamp,
         });
 
-        let pt = pool_type_from_u8(pool_type)?;
+        let params = ctx.accounts.protocol_config.params.for_pool(pool_type)?;
         let pool = &mut ctx.accounts.pool_state;
         pool.round_number = pool
             .round_number
             .checked_add(1)
             .ok_or(ErrorCode::MathOverflow)?;
         pool.round_start_time = round_end;
-        pool.round_end_time = round_end + pt.duration();
+        pool.round_end_time = round_end + params.duration;
+        pool.params = params;
         pool.total_deposited = 0;
+        pool.reserve_matched = 0;
         pool.regular_count = 0;
+        pool.pending_payouts = pool
+            .pending_payouts
+            .checked_add(user_owed)
+            .ok_or(ErrorCode::MathOverflow)?;
         // free_bet_total and free_count intentionally preserved
 
         Ok(())
     }
 
-    /// Claim vested top prize. Permissionless — the protocol cron calls this daily.
-    ///
-    /// Vesting schedule: 5% per day over 20 days.
-    /// elapsed_days = (now - draw_timestamp) / 86400
-    /// vested = total_amount × min(elapsed_days + 1, 20) / 20
-    /// claimable = vested - already_claimed
-    pub fn claim_prize_vesting(ctx: Context<ClaimPrizeVesting>, winner_index: u8) -> Result<()> {
+    /// Pull a deposit back from a refunded round. Permissionless — the tokens
+    /// go to the depositor's own token account and the deposit PDA's rent back
+    /// to the depositor, so the cron can also sweep refunds on users' behalf.
+    pub fn claim_refund(ctx: Context<ClaimRefund>, pool_type: u8, round_number: u64) -> Result<()> {
         let clock = Clock::get()?;
-        let wi = winner_index as usize;
-        require!(wi < 6, ErrorCode::InvalidWinnerIndex);
-
-        let draw = &mut ctx.accounts.draw_result;
-        let total_amount = draw.top_amounts[wi];
-        let already_claimed = draw.top_claimed[wi];
-
-        require!(
-            already_claimed < total_amount,
-            ErrorCode::AlreadyFullyClaimed
-        );
+        let amount = ctx.accounts.user_deposit.amount;
 
-        // Verify winner_token_account belongs to the recorded winner
-        require!(
-            ctx.accounts.winner_token_account.owner == draw.top_winners[wi],
-            ErrorCode::WinnerTokenMismatch
+        let pool_bump = ctx.accounts.pool_state.bump;
+        let pool_seeds: &[&[u8]] = &[b"pool", &[pool_type], &[pool_bump]];
+        let signer = &[pool_seeds];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.pool_vault.to_account_info(),
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.pool_state.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        let pool = &mut ctx.accounts.pool_state;
+        pool.pending_payouts = pool
+            .pending_payouts
+            .checked_sub(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let refund = &mut ctx.accounts.round_refund;
+        refund.claimed_count = refund
+            .claimed_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        refund.claimed_amount = refund
+            .claimed_amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let stats = &mut ctx.accounts.player_stats;
+        stats.open(ctx.accounts.user.key(), ctx.bumps.player_stats);
+        stats.record_refund(amount)?;
+
+        emit!(RefundClaimed {
+            pool_type,
+            round_number,
+            user: ctx.accounts.user.key(),
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Read-only invariant check: pool_vault must hold exactly
+    /// PoolState::accounted_total(). Fails with PoolVaultImbalance otherwise;
+    /// monitoring simulates this per pool.
+    pub fn check_pool_balance(ctx: Context<CheckPoolBalance>) -> Result<()> {
+        let accounted = ctx.accounts.pool_state.accounted_total()?;
+        let actual = ctx.accounts.pool_vault.amount;
+        msg!("pool_vault: {} accounted: {}", actual, accounted);
+        require!(actual == accounted, ErrorCode::PoolVaultImbalance);
+        Ok(())
+    }
+
+    /// Claim vested top prize. Permissionless — the protocol cron calls this daily.
+    ///
+    /// Vesting schedule: 5% per day over 20 days.
+    /// elapsed_days = (now - draw_timestamp) / 86400
+    /// vested = total_amount × min(elapsed_days + 1, 20) / 20
+    /// claimable = vested - already_claimed
+    pub fn claim_prize_vesting(ctx: Context<ClaimPrizeVesting>, winner_index: u8) -> Result<()> {
+        require!(
+            !ctx.accounts.global_state.paused(PAUSE_PRIZES),
+            ErrorCode::ProtocolPaused
+        );
+        let clock = Clock::get()?;
+        let wi = winner_index as usize;
+        require!(wi < 6, ErrorCode::InvalidWinnerIndex);
+
+        let draw = &mut ctx.accounts.draw_result;
+        require!(draw.draw_timestamp > 0, ErrorCode::DrawNotFinalized);
+        let total_amount = draw.top_amounts[wi];
+        let already_claimed = draw.top_claimed[wi];
+
+        require!(
+            already_claimed < total_amount,
+            ErrorCode::AlreadyFullyClaimed
+        );
+
+        // Verify winner_token_account belongs to the recorded winner
+        require!(
+            ctx.accounts.winner_token_account.owner == draw.top_winners[wi],
+            ErrorCode::WinnerTokenMismatch
+        );
+        require!(
+            ctx.accounts.winner.key() == draw.top_winners[wi],
+            ErrorCode::WinnerMismatch
         );
 
         let elapsed_days = ((clock.unix_timestamp - draw.draw_timestamp) / 86_400) as u64;
@@ -1378,10 +3047,20 @@ pub mod royalpot {
             .checked_add(claimable)
             .ok_or(ErrorCode::MathOverflow)?;
 
+        // The win counts once, on the first vesting payout
+        let tier = match wi {
+            0 => PRIZE_TIER_1ST,
+            1..=2 => PRIZE_TIER_2ND,
+            _ => PRIZE_TIER_3RD,
+        };
+        let stats = &mut ctx.accounts.player_stats;
+        stats.open(draw.top_winners[wi], ctx.bumps.player_stats);
+        stats.record_win(claimable, (already_claimed == 0).then_some(tier))?;
+
         emit!(PrizeVestingClaimed {
             pool_type: draw.pool_type,
             round_number: draw.round_number,
-            winner_index: winner_index,
+            winner_index,
             winner: draw.top_winners[wi],
             claimed_amount: claimable,
             total_claimed: draw.top_claimed[wi],
@@ -1392,8 +3071,104 @@ pub mod royalpot {
         Ok(())
     }
 
+    /// Pay participant `participant_index` its lucky or universal prize for a
+    /// finalized round. Permissionless — tokens only ever go to the depositor's
+    /// associated token account, so a bad account can neither redirect the
+    /// prize nor block anyone else's.
+    ///
+    /// `deposit` is the participant's UserDeposit (index < regular_count) or
+    /// FreeDeposit (consumed by this round).
+    pub fn claim_round_prize(
+        ctx: Context<ClaimRoundPrize>,
+        pool_type: u8,
+        round_number: u64,
+        participant_index: u32,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.global_state.paused(PAUSE_PRIZES),
+            ErrorCode::ProtocolPaused
+        );
+        let clock = Clock::get()?;
+
+        let draw = &mut ctx.accounts.draw_result;
+        require!(draw.draw_timestamp > 0, ErrorCode::DrawNotFinalized);
+        require!(
+            participant_index < draw.participant_count,
+            ErrorCode::InvalidParticipant
+        );
+        require!(
+            !draw.is_prize_claimed(participant_index),
+            ErrorCode::RoundPrizeAlreadyClaimed
+        );
+        let amount = draw.round_prize(participant_index);
+        require!(amount > 0, ErrorCode::NoRoundPrize);
+
+        let set = ParticipantSet {
+            pool_type,
+            round_number,
+            regular_count: draw.regular_count,
+            free_count: draw.participant_count - draw.regular_count,
+        };
+        let user = set.verify(&ctx.accounts.deposit, participant_index)?;
+        require!(ctx.accounts.user.key() == user, ErrorCode::WinnerMismatch);
+
+        require!(
+            ctx.accounts.user_token_account.key()
+                == get_associated_token_address(&user, &ctx.accounts.pool_vault.mint),
+            ErrorCode::NotDepositorAta
+        );
+
+        draw.set_prize_claimed(participant_index);
+
+        let pool = &mut ctx.accounts.pool_state;
+        pool.pending_payouts = pool
+            .pending_payouts
+            .checked_sub(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let pool_bump = ctx.accounts.pool_state.bump;
+        let pool_seeds: &[&[u8]] = &[b"pool", &[pool_type], &[pool_bump]];
+        let signer = &[pool_seeds];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.pool_vault.to_account_info(),
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.pool_state.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        // Top-prize slots have no round prize, so any listed index is lucky
+        let tier = if draw.winner_indices.contains(&participant_index) {
+            PRIZE_TIER_LUCKY
+        } else {
+            PRIZE_TIER_UNIVERSAL
+        };
+        let stats = &mut ctx.accounts.player_stats;
+        stats.open(user, ctx.bumps.player_stats);
+        stats.record_win(amount, Some(tier))?;
+
+        emit!(RoundPrizeClaimed {
+            pool_type,
+            round_number,
+            participant_index,
+            user,
+            amount,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
     /// Register user for one free bet (one-time per wallet).
     pub fn claim_free_airdrop(ctx: Context<ClaimFreeAirdrop>) -> Result<()> {
+        require!(
+            !ctx.accounts.global_state.paused(PAUSE_AIRDROP),
+            ErrorCode::ProtocolPaused
+        );
         let claim = &mut ctx.accounts.airdrop_claim;
         claim.user = ctx.accounts.user.key();
         claim.free_bet_available = true;
@@ -1402,7 +3177,8 @@ pub mod royalpot {
         Ok(())
     }
 
-    /// Pay 8% referral reward to the referrer stored in a deposit PDA.
+    /// Credit the 8% referral reward of a deposit to its referrer's
+    /// ReferralLedger. Tokens move later, in withdraw_referral_earnings.
     ///
     /// Permissionless — the protocol cron calls this after every successful draw
     /// for each deposit that has a non-default referrer.
@@ -1411,12 +3187,17 @@ pub mod royalpot {
     ///   - draw_result PDA for (pool_type, round_number) must exist (draw was successful)
     ///   - dep.referrer must be non-default (there is a referrer)
     ///   - referrer_token_account.key() must match dep.referrer (no spoofing)
-    ///   - dep.referrer is cleared to Pubkey::default() after payment (no double-claim)
+    ///   - dep.referrer is cleared to Pubkey::default() once credited (no double-claim)
     pub fn claim_referral(
         ctx: Context<ClaimReferral>,
         pool_type: u8,
         round_number: u64,
     ) -> Result<()> {
+        require!(
+            !ctx.accounts.global_state.paused(PAUSE_REFERRAL),
+            ErrorCode::ProtocolPaused
+        );
+
         // Verify draw_result matches the claimed pool/round (draw was successful, not refunded)
         require!(
             ctx.accounts.draw_result.pool_type == pool_type,
@@ -1446,34 +3227,97 @@ pub mod royalpot {
             .checked_div(BASE)
             .ok_or(ErrorCode::MathOverflow)?;
 
-        if referral_amount > 0 && ctx.accounts.referral_vault.amount >= referral_amount {
-            let gs_bump = ctx.accounts.global_state.bump;
-            let gs_seeds: &[&[u8]] = &[b"global_state", &[gs_bump]];
-            let signer = &[gs_seeds];
-            token::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    Transfer {
-                        from: ctx.accounts.referral_vault.to_account_info(),
-                        to: ctx.accounts.referrer_token_account.to_account_info(),
-                        authority: ctx.accounts.global_state.to_account_info(),
-                    },
-                    signer,
-                ),
-                referral_amount,
-            )?;
-        }
+        let ledger = &mut ctx.accounts.referral_ledger;
+        ledger.referrer = dep.referrer;
+        ledger.bump = ctx.bumps.referral_ledger;
+        ledger.owed = ledger
+            .owed
+            .checked_add(referral_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        ledger.total_earned = ledger
+            .total_earned
+            .checked_add(referral_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let health = &mut ctx.accounts.vault_health;
+        let owed = &mut health.owed[VAULT_KIND_REFERRAL as usize];
+        *owed = owed
+            .checked_add(referral_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit!(ReferralCredited {
+            referrer: dep.referrer,
+            user: dep.user,
+            pool_type,
+            round_number,
+            amount: referral_amount,
+            owed: ledger.owed,
+        });
 
         // Clear referrer to prevent double-claim
         dep.referrer = Pubkey::default();
         Ok(())
     }
 
+    /// Pay a referrer what its ReferralLedger is owed, up to what
+    /// referral_vault holds; the remainder stays owed for a later call.
+    /// Signed by the owner of the referrer token account.
+    pub fn withdraw_referral_earnings(ctx: Context<WithdrawReferralEarnings>) -> Result<()> {
+        require!(
+            !ctx.accounts.global_state.paused(PAUSE_REFERRAL),
+            ErrorCode::ProtocolPaused
+        );
+
+        let ledger = &mut ctx.accounts.referral_ledger;
+        let vault_balance = ctx.accounts.referral_vault.amount;
+        let amount = ledger.owed.min(vault_balance);
+        require!(amount > 0, ErrorCode::NothingToClaim);
+
+        let gs_bump = ctx.accounts.global_state.bump;
+        let gs_seeds: &[&[u8]] = &[b"global_state", &[gs_bump]];
+        let signer = &[gs_seeds];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.referral_vault.to_account_info(),
+                    to: ctx.accounts.referrer_token_account.to_account_info(),
+                    authority: ctx.accounts.global_state.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        ledger.owed -= amount;
+        ledger.total_paid = ledger
+            .total_paid
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let now = Clock::get()?.unix_timestamp;
+        let health = &mut ctx.accounts.vault_health;
+        let owed = &mut health.owed[VAULT_KIND_REFERRAL as usize];
+        *owed = owed.saturating_sub(amount);
+        health.observe_referral(vault_balance - amount, now);
+
+        emit!(ReferralEarningsWithdrawn {
+            referrer: ledger.referrer,
+            amount,
+            remaining: ledger.owed,
+            timestamp: now,
+        });
+        Ok(())
+    }
+
     /// Claim 2% referee bonus for a user's first deposit.
     ///
     /// Permissionless — user can call this anytime after their first deposit with a referrer.
     /// Awards a 2% bonus based on the first deposit amount.
     pub fn claim_referee_bonus(ctx: Context<ClaimRefereeBonus>) -> Result<()> {
+        require!(
+            !ctx.accounts.global_state.paused(PAUSE_REFERRAL),
+            ErrorCode::ProtocolPaused
+        );
+
         let claim = &mut ctx.accounts.referee_bonus_claim;
 
         // Verify that bonus hasn't been claimed yet
@@ -1490,8 +3334,10 @@ pub mod royalpot {
             .checked_div(BASE)
             .ok_or(ErrorCode::MathOverflow)?;
 
-        // Transfer bonus from referral_vault to user
-        if bonus_amount > 0 && ctx.accounts.referral_vault.amount >= bonus_amount {
+        // Transfer bonus from referral_vault to user; owe it if the vault is short
+        let now = Clock::get()?.unix_timestamp;
+        let mut vault_balance = ctx.accounts.referral_vault.amount;
+        if bonus_amount > 0 && vault_balance >= bonus_amount {
             let gs_bump = ctx.accounts.global_state.bump;
             let gs_seeds: &[&[u8]] = &[b"global_state", &[gs_bump]];
             let signer = &[gs_seeds];
@@ -1507,7 +3353,21 @@ pub mod royalpot {
                 ),
                 bonus_amount,
             )?;
+            vault_balance -= bonus_amount;
+        } else if bonus_amount > 0 {
+            health::record_iou(
+                &mut ctx.accounts.vault_health,
+                &mut ctx.accounts.iou,
+                ctx.bumps.iou,
+                VAULT_KIND_REFERRAL,
+                ctx.accounts.user_token_account.key(),
+                bonus_amount,
+                now,
+            )?;
         }
+        ctx.accounts
+            .vault_health
+            .observe_referral(vault_balance, now);
 
         // Mark as claimed to prevent double-claim
         claim.has_claimed = true;
@@ -1523,6 +3383,73 @@ pub mod royalpot {
         Ok(())
     }
 
+    // ----------------------------------------------------------
+    // Rent reclamation
+    // ----------------------------------------------------------
+
+    /// Close a drawn round's UserDeposit once nothing depends on it: the draw is
+    /// finalized, the round prize (if any) claimed and the referral (if any) paid.
+    /// Permissionless — rent goes to the depositor, who paid it.
+    /// Refunded rounds close their deposits in claim_refund instead.
+    pub fn close_user_deposit(ctx: Context<CloseUserDeposit>) -> Result<()> {
+        let dep = &ctx.accounts.user_deposit;
+        let draw = &mut ctx.accounts.draw_result;
+        require!(draw.draw_timestamp > 0, ErrorCode::DrawNotFinalized);
+        require!(dep.referrer == Pubkey::default(), ErrorCode::ReferralUnpaid);
+        require!(
+            draw.round_prize_settled(dep.entry_index),
+            ErrorCode::RoundPrizeUnclaimed
+        );
+
+        draw.open_entries = draw
+            .open_entries
+            .checked_sub(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Close a FreeDeposit consumed by a successful draw, once its round prize
+    /// (if any) is claimed. Permissionless — rent goes to the user, who paid it.
+    pub fn close_free_deposit(ctx: Context<CloseFreeDeposit>) -> Result<()> {
+        let dep = &ctx.accounts.free_deposit;
+        require!(!dep.is_active, ErrorCode::FreeBetStillActive);
+
+        let draw = &mut ctx.accounts.draw_result;
+        let index = draw
+            .regular_count
+            .checked_add(dep.entry_index)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            draw.round_prize_settled(index),
+            ErrorCode::RoundPrizeUnclaimed
+        );
+
+        draw.open_entries = draw
+            .open_entries
+            .checked_sub(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Close a DrawResult after all six top prizes have fully vested and every
+    /// deposit of the round has been closed. Only the original payer may close
+    /// it, since claim_referral relies on this record while referrals are unpaid.
+    pub fn close_draw_result(ctx: Context<CloseDrawResult>) -> Result<()> {
+        let draw = &ctx.accounts.draw_result;
+        require!(
+            draw.top_claimed == draw.top_amounts,
+            ErrorCode::TopPrizesOutstanding
+        );
+        require!(draw.open_entries == 0, ErrorCode::DepositsOutstanding);
+
+        let pool = &mut ctx.accounts.pool_state;
+        pool.open_draw_results = pool
+            .open_draw_results
+            .checked_sub(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
     // ----------------------------------------------------------
     // Staking (delegated)
     // ----------------------------------------------------------
@@ -1554,12 +3481,78 @@ pub mod royalpot {
     pub fn initialize_airdrop(ctx: Context<InitializeAirdrop>, total_airdrop: u64) -> Result<()> {
         airdrop::initialize_airdrop(ctx, total_airdrop)
     }
-    pub fn record_profit(ctx: Context<RecordProfit>, profit_amount: u64) -> Result<()> {
-        airdrop::record_profit(ctx, profit_amount)
+    pub fn record_profit(ctx: Context<RecordProfit>) -> Result<()> {
+        airdrop::record_profit(ctx)
     }
     pub fn claim_profit_airdrop(ctx: Context<ClaimProfitAirdrop>) -> Result<()> {
         airdrop::claim_profit_airdrop(ctx)
     }
+    pub fn start_airdrop_season(
+        ctx: Context<StartAirdropSeason>,
+        budget: u64,
+        start: i64,
+        end: i64,
+    ) -> Result<()> {
+        airdrop::start_airdrop_season(ctx, budget, start, end)
+    }
+    pub fn sweep_unclaimed(ctx: Context<SweepUnclaimed>) -> Result<()> {
+        airdrop::sweep_unclaimed(ctx)
+    }
+    pub fn create_distribution(
+        ctx: Context<CreateDistribution>,
+        root: [u8; 32],
+        total: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        airdrop::create_distribution(ctx, root, total, expires_at)
+    }
+    pub fn close_distribution(ctx: Context<CloseDistribution>) -> Result<()> {
+        airdrop::close_distribution(ctx)
+    }
+    pub fn claim_distribution(
+        ctx: Context<ClaimDistribution>,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        airdrop::claim_distribution(ctx, index, amount, proof)
+    }
+
+    // ----------------------------------------------------------
+    // Treasury (delegated)
+    // ----------------------------------------------------------
+    pub fn initialize_treasury(
+        ctx: Context<InitializeTreasury>,
+        config: treasury::TreasuryConfig,
+    ) -> Result<()> {
+        treasury::initialize_treasury(ctx, config)
+    }
+    pub fn route_platform_fees(ctx: Context<RoutePlatformFees>) -> Result<()> {
+        treasury::route_platform_fees(ctx)
+    }
+    pub fn treasury_withdraw(
+        ctx: Context<TreasuryWithdraw>,
+        op_id: u64,
+        withdrawal: treasury::TreasuryWithdrawal,
+    ) -> Result<()> {
+        treasury::treasury_withdraw(ctx, op_id, withdrawal)
+    }
+
+    // ----------------------------------------------------------
+    // Vault health (delegated)
+    // ----------------------------------------------------------
+    pub fn initialize_vault_health(
+        ctx: Context<InitializeVaultHealth>,
+        floors: health::VaultFloors,
+    ) -> Result<()> {
+        health::initialize_vault_health(ctx, floors)
+    }
+    pub fn check_vault_health(ctx: Context<CheckVaultHealth>) -> Result<health::VaultHealthReport> {
+        health::check_vault_health(ctx)
+    }
+    pub fn claim_iou(ctx: Context<ClaimIou>, kind: u8) -> Result<()> {
+        health::claim_iou(ctx, kind)
+    }
 
     // ----------------------------------------------------------
     // Vesting (for staking prizes / team vesting)
@@ -1630,35 +3623,501 @@ pub mod royalpot {
 }
 
 // ============================================================
-// Helper: read participant pubkey from deposit PDA (remaining_accounts)
+// Helper: admin authorization
 // ============================================================
-fn read_participant_pubkey(
-    pda_acc: &AccountInfo,
-    is_regular: bool,
-    pool_type: u8,
-    round_number: u64,
-) -> Result<Pubkey> {
-    if is_regular {
-        let dep = UserDeposit::try_deserialize(&mut pda_acc.data.borrow().as_ref())?;
-        require!(dep.pool_type == pool_type, ErrorCode::InvalidParticipant);
+
+/// How the signer of an admin instruction is authorized.
+enum AdminSigner {
+    /// GlobalState.authority itself signed
+    Authority,
+    /// GlobalState.authority is the AdminCouncil PDA and this member signed
+    Member(u8),
+}
+
+fn admin_signer(
+    state: &GlobalState,
+    signer: &Pubkey,
+    council: Option<&Account<AdminCouncil>>,
+) -> Result<AdminSigner> {
+    if *signer == state.authority {
+        return Ok(AdminSigner::Authority);
+    }
+    let council = council.ok_or(ErrorCode::Unauthorized)?;
+    require!(council.key() == state.authority, ErrorCode::Unauthorized);
+    let index = council
+        .member_index(signer)
+        .ok_or(ErrorCode::Unauthorized)?;
+    Ok(AdminSigner::Member(index))
+}
+
+/// `signer` may act as `authority`: it is that key, or `authority` is the
+/// AdminCouncil PDA and `signer` one of its members.
+fn is_authority(
+    authority: &Pubkey,
+    signer: &Pubkey,
+    council: Option<&Account<AdminCouncil>>,
+) -> bool {
+    signer == authority
+        || council.is_some_and(|c| c.key() == *authority && c.member_index(signer).is_some())
+}
+
+/// Removes ready operation `id` from the queue for `signer`, enforcing the
+/// council threshold when GlobalState.authority is the AdminCouncil.
+fn take_queued_op(
+    queue: &mut TimelockQueue,
+    state: &GlobalState,
+    signer: &Pubkey,
+    council: Option<&Account<AdminCouncil>>,
+    id: u64,
+    payload: &[u8],
+    now: i64,
+) -> Result<TimelockOp> {
+    let admin = admin_signer(state, signer, council)?;
+    let op = queue.take_ready(id, payload, now)?;
+    if let AdminSigner::Member(_) = admin {
+        let council = council.ok_or(ErrorCode::Unauthorized)?;
         require!(
-            dep.round_number == round_number,
-            ErrorCode::WrongRoundNumber
+            council.approved(op.approvals),
+            ErrorCode::InsufficientApprovals
         );
-        Ok(dep.user)
-    } else {
-        let dep = FreeDeposit::try_deserialize(&mut pda_acc.data.borrow().as_ref())?;
-        require!(dep.pool_type == pool_type, ErrorCode::InvalidParticipant);
-        require!(dep.is_active, ErrorCode::InvalidParticipant);
-        Ok(dep.user)
     }
+    Ok(op)
 }
 
-// ============================================================
-// Account Contexts
-// ============================================================
+/// A state account whose authority can be handed over.
+enum AuthorityTarget {
+    Global(GlobalState),
+    Staking(staking::StakingState),
+    Airdrop(airdrop::AirdropState),
+}
 
-#[derive(Accounts)]
+impl AuthorityTarget {
+    /// Deserialize by discriminator; the account must be owned by this program.
+    fn load(info: &AccountInfo) -> Result<Self> {
+        require!(info.owner == &crate::ID, ErrorCode::InvalidAuthorityTarget);
+        let data = info.try_borrow_data()?;
+        if let Ok(state) = GlobalState::try_deserialize(&mut &data[..]) {
+            return Ok(Self::Global(state));
+        }
+        if let Ok(state) = staking::StakingState::try_deserialize(&mut &data[..]) {
+            return Ok(Self::Staking(state));
+        }
+        if let Ok(state) = airdrop::AirdropState::try_deserialize(&mut &data[..]) {
+            return Ok(Self::Airdrop(state));
+        }
+        err!(ErrorCode::InvalidAuthorityTarget)
+    }
+
+    fn authority(&self) -> Pubkey {
+        match self {
+            Self::Global(s) => s.authority,
+            Self::Staking(s) => s.authority,
+            Self::Airdrop(s) => s.authority,
+        }
+    }
+
+    fn set_authority(&mut self, authority: Pubkey) {
+        match self {
+            Self::Global(s) => s.authority = authority,
+            Self::Staking(s) => s.authority = authority,
+            Self::Airdrop(s) => s.authority = authority,
+        }
+    }
+
+    fn store(&self, info: &AccountInfo) -> Result<()> {
+        let mut data = info.try_borrow_mut_data()?;
+        match self {
+            Self::Global(s) => s.try_serialize(&mut &mut data[..]),
+            Self::Staking(s) => s.try_serialize(&mut &mut data[..]),
+            Self::Airdrop(s) => s.try_serialize(&mut &mut data[..]),
+        }
+    }
+}
+
+// ============================================================
+// Helper: timelock payloads
+// ============================================================
+
+/// Rejects payloads execute_op could not apply, so a malformed operation
+/// fails at schedule time instead of after the timelock.
+fn validate_op_payload(op_type: u8, payload: &[u8]) -> Result<()> {
+    match op_type {
+        TIMELOCK_OP_PAUSE | TIMELOCK_OP_UNPAUSE => {
+            require!(
+                payload.len() == 1 && payload[0] != 0 && payload[0] & !PAUSE_ALL == 0,
+                ErrorCode::InvalidTimelockPayload
+            );
+        }
+        TIMELOCK_OP_GUARDIAN | TIMELOCK_OP_CLOSE_GLOBAL => {
+            Pubkey::try_from_slice(payload).map_err(|_| ErrorCode::InvalidTimelockPayload)?;
+        }
+        TIMELOCK_OP_CLOSE_POOL => {
+            let close = ClosePool::try_from_slice(payload)
+                .map_err(|_| ErrorCode::InvalidTimelockPayload)?;
+            pool_type_from_u8(close.pool_type)?;
+        }
+        TIMELOCK_OP_CONFIG => {
+            ProtocolParams::try_from_slice(payload)
+                .map_err(|_| ErrorCode::InvalidTimelockPayload)?
+                .validate()?;
+        }
+        TIMELOCK_OP_TREASURY_CONFIG => {
+            treasury::TreasuryConfig::try_from_slice(payload)
+                .map_err(|_| ErrorCode::InvalidTimelockPayload)?
+                .validate()?;
+        }
+        TIMELOCK_OP_VAULT_FLOORS => {
+            health::VaultFloors::try_from_slice(payload)
+                .map_err(|_| ErrorCode::InvalidTimelockPayload)?;
+        }
+        TIMELOCK_OP_AUTHORITY => {
+            let handover = AuthorityHandover::try_from_slice(payload)
+                .map_err(|_| ErrorCode::InvalidTimelockPayload)?;
+            require!(
+                handover.new_authority != Pubkey::default(),
+                ErrorCode::InvalidTimelockPayload
+            );
+        }
+        TIMELOCK_OP_TREASURY_WITHDRAW => {
+            let withdrawal = treasury::TreasuryWithdrawal::try_from_slice(payload)
+                .map_err(|_| ErrorCode::InvalidTimelockPayload)?;
+            require!(withdrawal.amount > 0, ErrorCode::InvalidTimelockPayload);
+        }
+        TIMELOCK_OP_COUNCIL => {
+            CouncilConfig::try_from_slice(payload)
+                .map_err(|_| ErrorCode::InvalidTimelockPayload)?
+                .validate()?;
+        }
+        TIMELOCK_OP_VAULT => {
+            let rotation = VaultRotation::try_from_slice(payload)
+                .map_err(|_| ErrorCode::InvalidTimelockPayload)?;
+            require!(
+                rotation.kind <= VAULT_KIND_PROFIT_AIRDROP,
+                ErrorCode::InvalidVaultKind
+            );
+        }
+        _ => return err!(ErrorCode::InvalidTimelockPayload),
+    }
+    Ok(())
+}
+
+// ============================================================
+// Helper: start a successful draw (shared by reveal_and_draw / execute_draw_vrf)
+// ============================================================
+
+/// Accounts written when a draw starts. Both draw contexts borrow into this
+/// so the selection logic lives in one place.
+struct DrawAccounts<'a, 'info> {
+    payer: Pubkey,
+    pool_state: &'a mut Account<'info, PoolState>,
+    draw_result: &'a mut Account<'info, DrawResult>,
+    draw_result_bump: u8,
+    draw_progress: &'a mut Account<'info, DrawProgress>,
+    draw_progress_bump: u8,
+}
+
+/// Validates the round, computes the prize split, selects 11 winners from
+/// `draw_seed` via randomness::pick_winners and opens DrawProgress.
+/// Token movements are left to crank_draw.
+fn begin_draw(accs: DrawAccounts<'_, '_>, draw_seed: [u8; 32]) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let pool_type = accs.pool_state.pool_type;
+    let round_number = accs.pool_state.round_number;
+    let round_end = accs.pool_state.round_end_time;
+    let regular_count = accs.pool_state.regular_count as usize;
+    let free_count = accs.pool_state.free_count as usize;
+    let total_deposited = accs.pool_state.total_deposited;
+    let free_bet_total = accs.pool_state.free_bet_total;
+    let prev_rollover = accs.pool_state.rollover;
+    let params = accs.pool_state.params;
+
+    require!(
+        clock.unix_timestamp >= round_end,
+        ErrorCode::TooEarlyForDraw
+    );
+
+    let total_count = regular_count + free_count;
+    require!(
+        (total_count as u32) >= params.min_participants,
+        ErrorCode::ShouldUseRefund
+    );
+
+    // -------------------------------------------------------
+    // Compute prize amounts
+    // -------------------------------------------------------
+    let total_pool = total_deposited
+        .checked_add(free_bet_total)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_add(prev_rollover)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let burn_amount = total_pool
+        .checked_mul(params.burn_rate)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(BASE)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let plat_amount = total_pool
+        .checked_mul(params.plat_rate)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(BASE)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // prize_pool = 95% of total_pool
+    let prize_pool = total_pool
+        .checked_sub(burn_amount)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_sub(plat_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // Every tier is a share of prize_pool; what they leave (rollover_bp, 5%)
+    // stays in the vault as rollover
+    let prize_1st = prize_pool
+        .checked_mul(params.prize_1st_bp)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(BASE)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let prize_2nd_each = prize_pool
+        .checked_mul(params.prize_2nd_each_bp)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(BASE)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let prize_3rd_each = prize_pool
+        .checked_mul(params.prize_3rd_each_bp)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(BASE)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let prize_lucky_each = prize_pool
+        .checked_mul(params.prize_lucky_each_bp)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(BASE)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let prize_universal_total = prize_pool
+        .checked_mul(params.prize_universal_bp)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(BASE)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // universal_count = total participants - 11 prize winners
+    let universal_count = total_count - 11;
+    let prize_universal_each = if universal_count > 0 {
+        prize_universal_total
+            .checked_div(universal_count as u64)
+            .ok_or(ErrorCode::MathOverflow)?
+    } else {
+        0u64
+    };
+
+    // Total going to prize_escrow = 1st + 2×2nd + 3×3rd
+    let top_prize_total = prize_1st
+        .checked_add(
+            prize_2nd_each
+                .checked_mul(2)
+                .ok_or(ErrorCode::MathOverflow)?,
+        )
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_add(
+            prize_3rd_each
+                .checked_mul(3)
+                .ok_or(ErrorCode::MathOverflow)?,
+        )
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // Left in pool_vault for claim_round_prize = 5×lucky + universal_count×universal
+    let round_prize_total = prize_lucky_each
+        .checked_mul(5)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_add(
+            prize_universal_each
+                .checked_mul(universal_count as u64)
+                .ok_or(ErrorCode::MathOverflow)?,
+        )
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // Everything not paid out carries to the next round: the rollover_bp share
+    // plus rounding dust. Keeps pool_vault equal to PoolState::accounted_total().
+    let rollover_amount = prize_pool
+        .checked_sub(top_prize_total)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_sub(round_prize_total)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    // -------------------------------------------------------
+    // Select 11 distinct winners
+    // -------------------------------------------------------
+    let picked = randomness::pick_winners(draw_seed, total_count)?;
+    let mut winner_indices = [0u32; 11];
+    for (dst, &idx) in winner_indices.iter_mut().zip(picked.iter()) {
+        *dst = idx as u32;
+    }
+
+    // Top winners are filled in by crank_draw; draw_timestamp = 0 until finalized
+    let draw_result = &mut *accs.draw_result;
+    draw_result.pool_type = pool_type;
+    draw_result.round_number = round_number;
+    draw_result.top_winners = [Pubkey::default(); 6];
+    draw_result.top_amounts = [
+        prize_1st,
+        prize_2nd_each,
+        prize_2nd_each,
+        prize_3rd_each,
+        prize_3rd_each,
+        prize_3rd_each,
+    ];
+    draw_result.top_claimed = [0u64; 6];
+    draw_result.draw_timestamp = 0;
+    draw_result.bump = accs.draw_result_bump;
+    draw_result.regular_count = regular_count as u32;
+    draw_result.participant_count = total_count as u32;
+    draw_result.winner_indices = winner_indices;
+    draw_result.lucky_amount_each = prize_lucky_each;
+    draw_result.universal_amount_each = prize_universal_each;
+    draw_result.payer = accs.payer;
+    draw_result.open_entries = total_count as u32;
+    draw_result.prize_claimed = vec![0u8; total_count.div_ceil(8)];
+
+    let progress = &mut *accs.draw_progress;
+    progress.pool_type = pool_type;
+    progress.round_number = round_number;
+    progress.phase = DRAW_PHASE_SETTLE;
+    progress.draw_seed = draw_seed;
+    progress.cursor = 0;
+    progress.total_pool = total_pool;
+    progress.burn_amount = burn_amount;
+    progress.platform_amount = plat_amount;
+    progress.rollover_amount = rollover_amount;
+    progress.top_prize_total = top_prize_total;
+    progress.round_prize_total = round_prize_total;
+    progress.lucky_winners = [Pubkey::default(); 5];
+    progress.bump = accs.draw_progress_bump;
+
+    accs.pool_state.draw_in_progress = true;
+    accs.pool_state.open_draw_results = accs
+        .pool_state
+        .open_draw_results
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(DrawStarted {
+        pool_type,
+        round_number,
+        participant_count: total_count as u32,
+        winner_indices,
+        draw_seed,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================
+// Helper: participant-set verification (crank_draw / claim_round_prize)
+// ============================================================
+
+/// One round's participant set: indices 0..regular_count are UserDeposit PDAs
+/// ordered by entry_index, regular_count..regular_count+free_count are FreeDeposit
+/// PDAs ordered by entry_index. index ↔ deposit PDA is a bijection, so a caller
+/// cannot skip, repeat or substitute an entry.
+struct ParticipantSet {
+    pool_type: u8,
+    round_number: u64,
+    regular_count: u32,
+    free_count: u32,
+}
+
+impl ParticipantSet {
+    fn total(&self) -> u32 {
+        self.regular_count + self.free_count
+    }
+
+    /// Verifies `dep_acc` is participant `index` and returns the depositor:
+    /// program-owned, canonical PDA for its seeds, same pool/round, and holding
+    /// exactly that entry position. A free entry counts while active or once
+    /// consumed by this round.
+    fn verify(&self, dep_acc: &AccountInfo, index: u32) -> Result<Pubkey> {
+        require!(index < self.total(), ErrorCode::ParticipantCountMismatch);
+        require!(dep_acc.owner == &crate::ID, ErrorCode::InvalidParticipant);
+
+        if index < self.regular_count {
+            let dep = UserDeposit::try_deserialize(&mut dep_acc.data.borrow().as_ref())?;
+            require!(
+                dep.pool_type == self.pool_type,
+                ErrorCode::InvalidParticipant
+            );
+            require!(
+                dep.round_number == self.round_number,
+                ErrorCode::WrongRoundNumber
+            );
+            require!(dep.entry_index == index, ErrorCode::InvalidParticipant);
+            let pda = Pubkey::create_program_address(
+                &[
+                    b"deposit",
+                    &[self.pool_type],
+                    dep.user.as_ref(),
+                    &self.round_number.to_le_bytes(),
+                    &[dep.bump],
+                ],
+                &crate::ID,
+            )
+            .map_err(|_| ErrorCode::InvalidParticipant)?;
+            require!(dep_acc.key() == pda, ErrorCode::InvalidParticipant);
+            Ok(dep.user)
+        } else {
+            let dep = FreeDeposit::try_deserialize(&mut dep_acc.data.borrow().as_ref())?;
+            require!(
+                dep.pool_type == self.pool_type,
+                ErrorCode::InvalidParticipant
+            );
+            require!(
+                dep.is_active || dep.consumed_round == self.round_number,
+                ErrorCode::InvalidParticipant
+            );
+            require!(
+                dep.entry_index == index - self.regular_count,
+                ErrorCode::InvalidParticipant
+            );
+            let pda = Pubkey::create_program_address(
+                &[
+                    b"free_deposit",
+                    &[self.pool_type],
+                    dep.user.as_ref(),
+                    &[dep.bump],
+                ],
+                &crate::ID,
+            )
+            .map_err(|_| ErrorCode::InvalidParticipant)?;
+            require!(dep_acc.key() == pda, ErrorCode::InvalidParticipant);
+            Ok(dep.user)
+        }
+    }
+
+    /// Verifies a page of deposit PDAs covering participants
+    /// first_index, first_index+1, … and returns their depositors.
+    fn verify_page(&self, accounts: &[AccountInfo], first_index: u32) -> Result<Vec<Pubkey>> {
+        // Duplicate account keys anywhere in the page
+        let mut keys: Vec<Pubkey> = accounts.iter().map(|a| a.key()).collect();
+        keys.sort_unstable();
+        require!(
+            keys.windows(2).all(|w| w[0] != w[1]),
+            ErrorCode::DuplicateParticipant
+        );
+
+        let mut users = Vec::with_capacity(accounts.len());
+        for (k, dep_acc) in accounts.iter().enumerate() {
+            let index = first_index
+                .checked_add(k as u32)
+                .ok_or(ErrorCode::MathOverflow)?;
+            users.push(self.verify(dep_acc, index)?);
+        }
+        Ok(users)
+    }
+}
+
+// ============================================================
+// Account Contexts
+// ============================================================
+
+#[derive(Accounts)]
 pub struct Initialize<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
@@ -1674,73 +4133,238 @@ pub struct Initialize<'info> {
 
     pub token_mint: Account<'info, Mint>,
 
-        /// The token account that funds free bets. Authority will be set to global_state PDA.
+    /// Platform fee accumulator
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"vault".as_ref(), &[VAULT_KIND_PLATFORM]],
+        bump,
+        token::mint = token_mint,
+        token::authority = global_state,
+    )]
+    pub platform_fee_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Funds free-bet entries; fund it by transfer after initialize
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"vault".as_ref(), &[VAULT_KIND_AIRDROP]],
+        bump,
+        token::mint = token_mint,
+        token::authority = global_state,
+    )]
+    pub airdrop_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Referral rewards source
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"vault".as_ref(), &[VAULT_KIND_REFERRAL]],
+        bump,
+        token::mint = token_mint,
+        token::authority = global_state,
+    )]
+    pub referral_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Daily-pool matching source
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"vault".as_ref(), &[VAULT_KIND_RESERVE]],
+        bump,
+        token::mint = token_mint,
+        token::authority = global_state,
+    )]
+    pub reserve_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Unvested top-prize escrow
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"vault".as_ref(), &[VAULT_KIND_PRIZE_ESCROW]],
+        bump,
+        token::mint = token_mint,
+        token::authority = global_state,
+    )]
+    pub prize_escrow_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeProtocolConfig<'info> {
     #[account(mut)]
-    pub airdrop_vault: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = global_state.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PROTOCOL_CONFIG_SIZE,
+        seeds = [b"protocol_config"],
+        bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
 
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct Pause<'info> {
+pub struct InitializeTimelockQueue<'info> {
+    #[account(mut)]
     pub authority: Signer<'info>,
 
     #[account(
-        mut,
         seeds = [b"global_state"],
         bump = global_state.bump,
         constraint = global_state.authority == authority.key() @ ErrorCode::Unauthorized,
     )]
     pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TimelockQueue::SIZE,
+        seeds = [b"timelock_queue"],
+        bump,
+    )]
+    pub timelock_queue: Box<Account<'info, TimelockQueue>>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct Unpause<'info> {
+pub struct InitializeAdminCouncil<'info> {
+    #[account(mut)]
     pub authority: Signer<'info>,
 
     #[account(
-        mut,
         seeds = [b"global_state"],
         bump = global_state.bump,
         constraint = global_state.authority == authority.key() @ ErrorCode::Unauthorized,
     )]
     pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AdminCouncil::SIZE,
+        seeds = [b"admin_council"],
+        bump,
+    )]
+    pub admin_council: Box<Account<'info, AdminCouncil>>,
+
+    pub system_program: Program<'info, System>,
 }
 
+/// Admin is GlobalState.authority or, under a council, any member
+/// (admin_council must then be passed).
 #[derive(Accounts)]
-pub struct CancelTimelock<'info> {
+pub struct ScheduleOp<'info> {
     pub authority: Signer<'info>,
 
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
     #[account(
         mut,
+        seeds = [b"timelock_queue"],
+        bump = timelock_queue.bump,
+    )]
+    pub timelock_queue: Box<Account<'info, TimelockQueue>>,
+
+    #[account(
+        seeds = [b"admin_council"],
+        bump = admin_council.bump,
+    )]
+    pub admin_council: Option<Box<Account<'info, AdminCouncil>>>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveOp<'info> {
+    pub member: Signer<'info>,
+
+    #[account(
         seeds = [b"global_state"],
         bump = global_state.bump,
-        constraint = global_state.authority == authority.key() @ ErrorCode::Unauthorized,
     )]
     pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"timelock_queue"],
+        bump = timelock_queue.bump,
+    )]
+    pub timelock_queue: Box<Account<'info, TimelockQueue>>,
+
+    #[account(
+        seeds = [b"admin_council"],
+        bump = admin_council.bump,
+    )]
+    pub admin_council: Box<Account<'info, AdminCouncil>>,
 }
 
 #[derive(Accounts)]
-pub struct CloseGlobalState<'info> {
+pub struct ExecuteOp<'info> {
     pub authority: Signer<'info>,
 
     #[account(
         mut,
         seeds = [b"global_state"],
         bump = global_state.bump,
-        constraint = global_state.authority == authority.key() @ ErrorCode::Unauthorized,
-        close = recipient,
     )]
     pub global_state: Account<'info, GlobalState>,
 
-    /// CHECK: Receives lamports
-    #[account(mut)]
-    pub recipient: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"timelock_queue"],
+        bump = timelock_queue.bump,
+    )]
+    pub timelock_queue: Box<Account<'info, TimelockQueue>>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"admin_council"],
+        bump = admin_council.bump,
+    )]
+    pub admin_council: Option<Box<Account<'info, AdminCouncil>>>,
+
+    /// Required for TIMELOCK_OP_TREASURY_CONFIG
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Option<Box<Account<'info, treasury::Treasury>>>,
+
+    /// Required for TIMELOCK_OP_VAULT_FLOORS
+    #[account(
+        mut,
+        seeds = [b"vault_health"],
+        bump = vault_health.bump,
+    )]
+    pub vault_health: Option<Box<Account<'info, health::VaultHealth>>>,
 }
 
 #[derive(Accounts)]
-#[instruction(pool_type: u8)]
-pub struct ClosePoolState<'info> {
+pub struct CancelOp<'info> {
     pub authority: Signer<'info>,
 
     #[account(
@@ -1751,42 +4375,838 @@ pub struct ClosePoolState<'info> {
 
     #[account(
         mut,
-        seeds = [b"pool", &[pool_type]],
-        bump = pool_state.bump,
-        close = recipient,
+        seeds = [b"timelock_queue"],
+        bump = timelock_queue.bump,
     )]
-    pub pool_state: Account<'info, PoolState>,
+    pub timelock_queue: Box<Account<'info, TimelockQueue>>,
 
-    /// CHECK: Receives lamports
-    #[account(mut)]
-    pub recipient: AccountInfo<'info>,
+    #[account(
+        seeds = [b"admin_council"],
+        bump = admin_council.bump,
+    )]
+    pub admin_council: Option<Box<Account<'info, AdminCouncil>>>,
 }
 
 #[derive(Accounts)]
-#[instruction(pool_type: u8)]
-pub struct InitializePool<'info> {
+pub struct GuardianPause<'info> {
+    pub guardian: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = global_state.guardian != Pubkey::default()
+            && global_state.guardian == guardian.key() @ ErrorCode::Unauthorized,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub proposer: Signer<'info>,
+
+    /// CHECK: GlobalState, StakingState or AirdropState — owner and
+    /// discriminator checked by AuthorityTarget::load
+    pub target: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init_if_needed,
+        payer = proposer,
+        space = AUTHORITY_PROPOSAL_SIZE,
+        seeds = [b"authority_proposal", target.key().as_ref()],
+        bump,
+    )]
+    pub authority_proposal: Account<'info, AuthorityProposal>,
+
+    #[account(
+        seeds = [b"admin_council"],
+        bump = admin_council.bump,
+    )]
+    pub admin_council: Option<Box<Account<'info, AdminCouncil>>>,
+
+    /// Required for a council-held target
+    #[account(
+        mut,
+        seeds = [b"timelock_queue"],
+        bump = timelock_queue.bump,
+    )]
+    pub timelock_queue: Option<Box<Account<'info, TimelockQueue>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(mut)]
+    pub new_authority: Signer<'info>,
+
+    /// CHECK: must match authority_proposal.target; type checked by AuthorityTarget::load
+    #[account(mut, address = authority_proposal.target)]
+    pub target: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"authority_proposal", target.key().as_ref()],
+        bump = authority_proposal.bump,
+        close = new_authority,
+    )]
+    pub authority_proposal: Account<'info, AuthorityProposal>,
+
+    #[account(
+        seeds = [b"admin_council"],
+        bump = admin_council.bump,
+    )]
+    pub admin_council: Option<Box<Account<'info, AdminCouncil>>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseGlobalState<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        close = recipient,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"timelock_queue"],
+        bump = timelock_queue.bump,
+    )]
+    pub timelock_queue: Box<Account<'info, TimelockQueue>>,
+
+    #[account(
+        seeds = [b"admin_council"],
+        bump = admin_council.bump,
+    )]
+    pub admin_council: Option<Box<Account<'info, AdminCouncil>>>,
+
+    /// CHECK: pool PDA; must already be closed (empty)
+    #[account(seeds = [b"pool".as_ref(), &[0]], bump)]
+    pub pool_30min: UncheckedAccount<'info>,
+
+    /// CHECK: pool PDA; must already be closed (empty)
+    #[account(seeds = [b"pool".as_ref(), &[1]], bump)]
+    pub pool_hourly: UncheckedAccount<'info>,
+
+    /// CHECK: pool PDA; must already be closed (empty)
+    #[account(seeds = [b"pool".as_ref(), &[2]], bump)]
+    pub pool_daily: UncheckedAccount<'info>,
+
+    // Registered vaults; each must be empty
+    #[account(address = global_state.platform_fee_vault @ ErrorCode::VaultMismatch)]
+    pub platform_fee_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = global_state.airdrop_vault @ ErrorCode::VaultMismatch)]
+    pub airdrop_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = global_state.referral_vault @ ErrorCode::VaultMismatch)]
+    pub referral_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = global_state.reserve_vault @ ErrorCode::VaultMismatch)]
+    pub reserve_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = global_state.prize_escrow_vault @ ErrorCode::VaultMismatch)]
+    pub prize_escrow_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: AirdropState PDA; if it exists, its vault must be passed and empty
+    #[account(seeds = [b"airdrop_state"], bump)]
+    pub airdrop_state: UncheckedAccount<'info>,
+
+    /// AirdropState.vault (VAULT_KIND_PROFIT_AIRDROP); checked in the handler
+    pub profit_airdrop_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: Receives lamports; bound by the queued operation's payload
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(pool_type: u8)]
+pub struct ClosePoolState<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", &[pool_type]],
+        bump = pool_state.bump,
+        close = recipient,
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        constraint = pool_vault.key() == pool_state.vault @ ErrorCode::VaultMismatch,
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"timelock_queue"],
+        bump = timelock_queue.bump,
+    )]
+    pub timelock_queue: Box<Account<'info, TimelockQueue>>,
+
+    #[account(
+        seeds = [b"admin_council"],
+        bump = admin_council.bump,
+    )]
+    pub admin_council: Option<Box<Account<'info, AdminCouncil>>>,
+
+    /// CHECK: Receives lamports; bound by the queued operation's payload
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(op_id: u64, rotation: VaultRotation)]
+pub struct RotateVault<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(address = global_state.token_mint @ ErrorCode::MintMismatch)]
+    pub token_mint: Account<'info, Mint>,
+
+    /// The vault being replaced (checked against the registry in the handler)
+    #[account(mut)]
+    pub old_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Required to rotate VAULT_KIND_PROFIT_AIRDROP
+    #[account(
+        mut,
+        seeds = [b"airdrop_state"],
+        bump = airdrop_state.bump,
+    )]
+    pub airdrop_state: Option<Box<Account<'info, airdrop::AirdropState>>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"vault".as_ref(), &[rotation.kind], &rotation.nonce.to_le_bytes()],
+        bump,
+        token::mint = token_mint,
+        token::authority = global_state,
+    )]
+    pub new_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"timelock_queue"],
+        bump = timelock_queue.bump,
+    )]
+    pub timelock_queue: Box<Account<'info, TimelockQueue>>,
+
+    #[account(
+        seeds = [b"admin_council"],
+        bump = admin_council.bump,
+    )]
+    pub admin_council: Option<Box<Account<'info, AdminCouncil>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateGlobalState<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: May be on an older layout; parsed by migration::read_global_state
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump,
+    )]
+    pub global_state: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"admin_council"],
+        bump = admin_council.bump,
+    )]
+    pub admin_council: Option<Box<Account<'info, AdminCouncil>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAirdropState<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: May be on an older layout; parsed by migration::read_airdrop_state
+    #[account(
+        mut,
+        seeds = [b"airdrop_state"],
+        bump,
+    )]
+    pub airdrop_state: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(address = global_state.token_mint @ ErrorCode::MintMismatch)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"vault".as_ref(), &[VAULT_KIND_PROFIT_AIRDROP]],
+        bump,
+        token::mint = token_mint,
+        token::authority = global_state,
+    )]
+    pub airdrop_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Profit-airdrop vault owned by legacy_airdrop_authority, if any
+    #[account(mut)]
+    pub legacy_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: pre-registry airdrop vault authority PDA
+    #[account(seeds = [b"airdrop"], bump)]
+    pub legacy_airdrop_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(pool_type: u8)]
+pub struct MigratePoolState<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: May be on an older layout; parsed by migration::read_pool_state
+    #[account(
+        mut,
+        seeds = [b"pool", &[pool_type]],
+        bump,
+    )]
+    pub pool_state: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"admin_council"],
+        bump = admin_council.bump,
+    )]
+    pub admin_council: Option<Box<Account<'info, AdminCouncil>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(pool_type: u8)]
+pub struct InitializePool<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = POOL_STATE_SIZE,
+        seeds = [b"pool".as_ref(), &[pool_type]],
+        bump,
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(address = global_state.token_mint @ ErrorCode::MintMismatch)]
+    pub token_mint: Account<'info, Mint>,
+
+    /// Token account for this pool, owned by the pool_state PDA
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"pool_vault".as_ref(), &[pool_type]],
+        bump,
+        token::mint = token_mint,
+        token::authority = pool_state,
+    )]
+    pub pool_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool".as_ref(), &[pool_state.pool_type]],
+        bump = pool_state.bump,
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        init,
+        payer = user,
+        space = USER_DEPOSIT_SIZE,
+        seeds = [
+            b"deposit".as_ref(),
+            &[pool_state.pool_type],
+            user.key().as_ref(),
+            &pool_state.round_number.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key(),
+        constraint = user_token_account.mint == pool_vault.mint,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = pool_vault.key() == pool_state.vault @ ErrorCode::VaultMismatch,
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// Reserve vault for daily 1:1 matching. Authority = global_state PDA.
+    #[account(
+        mut,
+        constraint = reserve_vault.key() == global_state.reserve_vault @ ErrorCode::ReserveVaultMismatch,
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+
+    /// Referee bonus claim account (optional, created manually in instruction if user has referrer)
+    /// CHECK: Validated in instruction body
+    pub referee_bonus_claim: UncheckedAccount<'info>,
+
+    /// Referrers are not recorded while referral_disabled
+    #[account(seeds = [b"vault_health"], bump = vault_health.bump)]
+    pub vault_health: Box<Account<'info, health::VaultHealth>>,
+
+    /// Created on the user's first entry
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = PLAYER_STATS_SIZE,
+        seeds = [b"player_stats", user.key().as_ref()],
+        bump,
+    )]
+    pub player_stats: Box<Account<'info, PlayerStats>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    // remaining_accounts[0] (optional, read-only): referrer's token account pubkey stored in PDA.
+    // No transfer happens at deposit time — referral is deferred to claim_referral().
+}
+
+#[derive(Accounts)]
+#[instruction(pool_type: u8)]
+pub struct UseFreeBet<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"pool".as_ref(), &[pool_type]],
+        bump = pool_state.bump,
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        mut,
+        seeds = [b"airdrop_claim", user.key().as_ref()],
+        bump = airdrop_claim.bump,
+    )]
+    pub airdrop_claim: Account<'info, AirdropClaim>,
+
+    #[account(
+        init,
+        payer = user,
+        space = FREE_DEPOSIT_SIZE,
+        seeds = [b"free_deposit".as_ref(), &[pool_type], user.key().as_ref()],
+        bump,
+    )]
+    pub free_deposit: Account<'info, FreeDeposit>,
+
+    #[account(
+        mut,
+        constraint = airdrop_vault.key() == global_state.airdrop_vault @ ErrorCode::AirdropVaultMismatch,
+    )]
+    pub airdrop_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = pool_vault.key() == pool_state.vault @ ErrorCode::VaultMismatch,
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+
+    #[account(mut, seeds = [b"vault_health"], bump = vault_health.bump)]
+    pub vault_health: Box<Account<'info, health::VaultHealth>>,
+
+    /// Created on the user's first entry
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = PLAYER_STATS_SIZE,
+        seeds = [b"player_stats", user.key().as_ref()],
+        bump,
+    )]
+    pub player_stats: Box<Account<'info, PlayerStats>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(pool_type: u8)]
+pub struct CommitDrawSeed<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = global_state.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        seeds = [b"pool".as_ref(), &[pool_type]],
+        bump = pool_state.bump,
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = DRAW_COMMIT_SIZE,
+        seeds = [
+            b"draw_commit".as_ref(),
+            &[pool_type],
+            pool_state.round_number.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub draw_commit: Account<'info, DrawCommit>,
+
+    #[account(address = global_state.token_mint @ ErrorCode::MintMismatch)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = committer_token.owner == authority.key() @ ErrorCode::Unauthorized,
+        constraint = committer_token.mint == token_mint.key() @ ErrorCode::MintMismatch,
+    )]
+    pub committer_token: Account<'info, TokenAccount>,
+
+    /// Bond escrow for this round, owned by the pool PDA
+    #[account(
+        init,
+        payer = authority,
+        seeds = [
+            b"draw_bond".as_ref(),
+            &[pool_type],
+            pool_state.round_number.to_le_bytes().as_ref(),
+        ],
+        bump,
+        token::mint = token_mint,
+        token::authority = pool_state,
+    )]
+    pub draw_bond: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// Box<Account<...>> keeps large accounts on the heap to stay within the
+// 4096-byte BPF stack frame limit in try_accounts().
+#[derive(Accounts)]
+pub struct RevealAndDraw<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool".as_ref(), &[pool_state.pool_type]],
+        bump = pool_state.bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    /// DrawResult PDA — created when the draw starts, records top-prize vesting data.
+    #[account(
+        init,
+        payer = caller,
+        space = draw_result_size(pool_state.regular_count + pool_state.free_count),
+        seeds = [
+            b"draw_result".as_ref(),
+            &[pool_state.pool_type],
+            pool_state.round_number.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub draw_result: Box<Account<'info, DrawResult>>,
+
+    /// DrawProgress PDA — drives crank_draw until the round is finalized.
+    #[account(
+        init,
+        payer = caller,
+        space = DRAW_PROGRESS_SIZE,
+        seeds = [
+            b"draw_progress".as_ref(),
+            &[pool_state.pool_type],
+            pool_state.round_number.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub draw_progress: Box<Account<'info, DrawProgress>>,
+
+    /// Commit for this round. Only the committer may reveal; rent returns to them.
+    #[account(
+        mut,
+        seeds = [
+            b"draw_commit".as_ref(),
+            &[pool_state.pool_type],
+            pool_state.round_number.to_le_bytes().as_ref(),
+        ],
+        bump = draw_commit.bump,
+        constraint = draw_commit.committer == caller.key() @ ErrorCode::Unauthorized,
+        close = caller,
+    )]
+    pub draw_commit: Box<Account<'info, DrawCommit>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"draw_bond".as_ref(),
+            &[pool_state.pool_type],
+            pool_state.round_number.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub draw_bond: Box<Account<'info, TokenAccount>>,
+
+    /// Receives the bond back
+    #[account(
+        mut,
+        constraint = committer_token.owner == caller.key() @ ErrorCode::PayoutAccountMismatch,
+        constraint = committer_token.mint == global_state.token_mint @ ErrorCode::MintMismatch,
+    )]
+    pub committer_token: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: SlotHashes sysvar, address-checked; parsed by randomness::slot_hash_at
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(pool_type: u8, round_number: u64)]
+pub struct CloseDrawCommit<'info> {
+    /// Anyone can call — the bond is either burned or returned to the committer
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [b"pool".as_ref(), &[pool_type]],
+        bump = pool_state.bump,
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"draw_commit".as_ref(),
+            &[pool_type],
+            round_number.to_le_bytes().as_ref(),
+        ],
+        bump = draw_commit.bump,
+    )]
+    pub draw_commit: Account<'info, DrawCommit>,
+
+    /// Bond escrow; omitted once the bond has been burned
+    #[account(
+        mut,
+        seeds = [
+            b"draw_bond".as_ref(),
+            &[pool_type],
+            round_number.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub draw_bond: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Receives the bond back; only needed when it is not forfeited
+    #[account(
+        mut,
+        constraint = committer_token.owner == draw_commit.committer @ ErrorCode::PayoutAccountMismatch,
+        constraint = committer_token.mint == global_state.token_mint @ ErrorCode::MintMismatch,
+    )]
+    pub committer_token: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Burned from when the bond is forfeited
+    #[account(mut, address = global_state.token_mint @ ErrorCode::MintMismatch)]
+    pub token_mint: Account<'info, Mint>,
+
+    /// CHECK: gets rent (and the bond unless forfeited) back
+    #[account(mut, address = draw_commit.committer)]
+    pub committer: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CrankDraw<'info> {
+    /// Anyone can call — permissionless (cron cranks until finalized)
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool".as_ref(), &[pool_state.pool_type]],
+        bump = pool_state.bump,
+    )]
+    pub pool_state: Box<Account<'info, PoolState>>,
+
+    #[account(
+        mut,
+        constraint = pool_vault.key() == pool_state.vault @ ErrorCode::VaultMismatch,
+    )]
+    pub pool_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = platform_vault.key() == global_state.platform_fee_vault @ ErrorCode::PlatformVaultMismatch,
+    )]
+    pub platform_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = prize_escrow_vault.key() == global_state.prize_escrow_vault @ ErrorCode::PrizeEscrowMismatch,
+    )]
+    pub prize_escrow_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"draw_result".as_ref(),
+            &[pool_state.pool_type],
+            pool_state.round_number.to_le_bytes().as_ref(),
+        ],
+        bump = draw_result.bump,
+    )]
+    pub draw_result: Box<Account<'info, DrawResult>>,
 
     #[account(
-        init,
-        payer = payer,
-        space = POOL_STATE_SIZE,
-        seeds = [b"pool".as_ref(), &[pool_type]],
-        bump,
+        mut,
+        seeds = [
+            b"draw_progress".as_ref(),
+            &[pool_state.pool_type],
+            pool_state.round_number.to_le_bytes().as_ref(),
+        ],
+        bump = draw_progress.bump,
     )]
-    pub pool_state: Account<'info, PoolState>,
+    pub draw_progress: Box<Account<'info, DrawProgress>>,
 
-    /// Token account for this pool. Authority must be pool_state PDA.
-    pub pool_vault: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
 
-    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct Deposit<'info> {
+pub struct ExecuteRefund<'info> {
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub caller: Signer<'info>,
 
     #[account(
         mut,
@@ -1795,27 +5215,6 @@ pub struct Deposit<'info> {
     )]
     pub pool_state: Account<'info, PoolState>,
 
-    #[account(
-        init,
-        payer = user,
-        space = USER_DEPOSIT_SIZE,
-        seeds = [
-            b"deposit".as_ref(),
-            &[pool_state.pool_type],
-            user.key().as_ref(),
-            &pool_state.round_number.to_le_bytes(),
-        ],
-        bump,
-    )]
-    pub user_deposit: Account<'info, UserDeposit>,
-
-    #[account(
-        mut,
-        constraint = user_token_account.owner == user.key(),
-        constraint = user_token_account.mint == pool_vault.mint,
-    )]
-    pub user_token_account: Account<'info, TokenAccount>,
-
     #[account(
         mut,
         constraint = pool_vault.key() == pool_state.vault @ ErrorCode::VaultMismatch,
@@ -1828,34 +5227,69 @@ pub struct Deposit<'info> {
     )]
     pub global_state: Account<'info, GlobalState>,
 
-    /// Reserve vault for daily 1:1 matching. Authority = global_state PDA.
     #[account(
         mut,
         constraint = reserve_vault.key() == global_state.reserve_vault @ ErrorCode::ReserveVaultMismatch,
     )]
     pub reserve_vault: Account<'info, TokenAccount>,
 
-    /// Referee bonus claim account (optional, created manually in instruction if user has referrer)
-    /// CHECK: Validated in instruction body
-    pub referee_bonus_claim: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Outcome record — init fails if the round was already refunded.
+    #[account(
+        init,
+        payer = caller,
+        space = ROUND_REFUND_SIZE,
+        seeds = [
+            b"round_refund".as_ref(),
+            &[pool_state.pool_type],
+            pool_state.round_number.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub round_refund: Account<'info, RoundRefund>,
+
+    /// Lets a full round whose VRF requests all timed out refund before REVEAL_WINDOW ends
+    #[account(seeds = [b"vrf_state"], bump = vrf_state.bump)]
+    pub vrf_state: Option<Account<'info, VrfState>>,
+
+    /// CHECK: this round's DrawCommit PDA; must be closed before a full round is refunded
+    #[account(
+        seeds = [
+            b"draw_commit".as_ref(),
+            &[pool_state.pool_type],
+            pool_state.round_number.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub draw_commit: UncheckedAccount<'info>,
 
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
-    // remaining_accounts[0] (optional, read-only): referrer's token account pubkey stored in PDA.
-    // No transfer happens at deposit time — referral is deferred to claim_referral().
 }
 
 #[derive(Accounts)]
-#[instruction(pool_type: u8)]
-pub struct UseFreeBet<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-
+pub struct CheckPoolBalance<'info> {
     #[account(
-        seeds = [b"global_state"],
-        bump = global_state.bump,
+        seeds = [b"pool".as_ref(), &[pool_state.pool_type]],
+        bump = pool_state.bump,
     )]
-    pub global_state: Account<'info, GlobalState>,
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(constraint = pool_vault.key() == pool_state.vault @ ErrorCode::VaultMismatch)]
+    pub pool_vault: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(pool_type: u8, round_number: u64)]
+pub struct ClaimRefund<'info> {
+    /// Anyone can call — tokens and rent only ever go to the depositor
+    #[account(mut)]
+    pub caller: Signer<'info>,
 
     #[account(
         mut,
@@ -1866,113 +5300,150 @@ pub struct UseFreeBet<'info> {
 
     #[account(
         mut,
-        seeds = [b"airdrop_claim", user.key().as_ref()],
-        bump = airdrop_claim.bump,
+        constraint = pool_vault.key() == pool_state.vault @ ErrorCode::VaultMismatch,
     )]
-    pub airdrop_claim: Account<'info, AirdropClaim>,
+    pub pool_vault: Account<'info, TokenAccount>,
 
+    /// Proves the round was refunded (not drawn).
     #[account(
-        init,
-        payer = user,
-        space = FREE_DEPOSIT_SIZE,
-        seeds = [b"free_deposit".as_ref(), &[pool_type], user.key().as_ref()],
-        bump,
+        mut,
+        seeds = [
+            b"round_refund".as_ref(),
+            &[pool_type],
+            round_number.to_le_bytes().as_ref(),
+        ],
+        bump = round_refund.bump,
     )]
-    pub free_deposit: Account<'info, FreeDeposit>,
+    pub round_refund: Account<'info, RoundRefund>,
 
+    /// Closed after the refund; rent returns to the depositor.
     #[account(
         mut,
-        constraint = airdrop_vault.key() == global_state.airdrop_vault @ ErrorCode::AirdropVaultMismatch,
+        seeds = [
+            b"deposit".as_ref(),
+            &[pool_type],
+            user.key().as_ref(),
+            &round_number.to_le_bytes(),
+        ],
+        bump = user_deposit.bump,
+        close = user,
     )]
-    pub airdrop_vault: Account<'info, TokenAccount>,
+    pub user_deposit: Account<'info, UserDeposit>,
+
+    /// CHECK: the depositor — bound to user_deposit by its PDA seeds
+    #[account(mut)]
+    pub user: UncheckedAccount<'info>,
 
     #[account(
         mut,
-        constraint = pool_vault.key() == pool_state.vault @ ErrorCode::VaultMismatch,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::PayoutAccountMismatch,
+        constraint = user_token_account.mint == pool_vault.mint @ ErrorCode::PayoutAccountMismatch,
     )]
-    pub pool_vault: Account<'info, TokenAccount>,
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    /// Normally created by deposit; the caller pays for pre-stats deposits
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = PLAYER_STATS_SIZE,
+        seeds = [b"player_stats", user.key().as_ref()],
+        bump,
+    )]
+    pub player_stats: Box<Account<'info, PlayerStats>>,
 
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
-// Box<Account<...>> keeps large accounts on the heap to stay within the
-// 4096-byte BPF stack frame limit in try_accounts().
 #[derive(Accounts)]
-pub struct ExecuteDraw<'info> {
-    #[account(mut)]
+pub struct CloseUserDeposit<'info> {
+    /// Anyone can call — rent goes to the depositor
     pub caller: Signer<'info>,
 
     #[account(
         mut,
-        seeds = [b"pool".as_ref(), &[pool_state.pool_type]],
-        bump = pool_state.bump,
-    )]
-    pub pool_state: Box<Account<'info, PoolState>>,
-
-    #[account(
-        mut,
-        constraint = pool_vault.key() == pool_state.vault @ ErrorCode::VaultMismatch,
+        seeds = [
+            b"deposit".as_ref(),
+            &[user_deposit.pool_type],
+            user.key().as_ref(),
+            &user_deposit.round_number.to_le_bytes(),
+        ],
+        bump = user_deposit.bump,
+        close = user,
     )]
-    pub pool_vault: Box<Account<'info, TokenAccount>>,
+    pub user_deposit: Account<'info, UserDeposit>,
 
+    /// CHECK: the depositor — bound to user_deposit by its PDA seeds
     #[account(mut)]
-    pub token_mint: Account<'info, Mint>,
+    pub user: UncheckedAccount<'info>,
 
+    /// Must exist — only drawn rounds qualify
     #[account(
         mut,
-        constraint = platform_vault.key() == global_state.platform_fee_vault @ ErrorCode::PlatformVaultMismatch,
+        seeds = [
+            b"draw_result".as_ref(),
+            &[user_deposit.pool_type],
+            user_deposit.round_number.to_le_bytes().as_ref(),
+        ],
+        bump = draw_result.bump,
     )]
-    pub platform_vault: Box<Account<'info, TokenAccount>>,
+    pub draw_result: Account<'info, DrawResult>,
+}
+
+#[derive(Accounts)]
+pub struct CloseFreeDeposit<'info> {
+    /// Anyone can call — rent goes to the user
+    pub caller: Signer<'info>,
 
     #[account(
         mut,
-        constraint = prize_escrow_vault.key() == global_state.prize_escrow_vault @ ErrorCode::PrizeEscrowMismatch,
+        seeds = [b"free_deposit".as_ref(), &[free_deposit.pool_type], user.key().as_ref()],
+        bump = free_deposit.bump,
+        close = user,
     )]
-    pub prize_escrow_vault: Box<Account<'info, TokenAccount>>,
+    pub free_deposit: Account<'info, FreeDeposit>,
 
-    #[account(
-        seeds = [b"global_state"],
-        bump = global_state.bump,
-    )]
-    pub global_state: Box<Account<'info, GlobalState>>,
+    /// CHECK: the free-bet owner — bound to free_deposit by its PDA seeds
+    #[account(mut)]
+    pub user: UncheckedAccount<'info>,
 
-    /// DrawResult PDA — created during this draw, records top-prize vesting data.
+    /// DrawResult of the round that consumed the entry
     #[account(
-        init,
-        payer = caller,
-        space = DRAW_RESULT_SIZE,
+        mut,
         seeds = [
             b"draw_result".as_ref(),
-            &[pool_state.pool_type],
-            pool_state.round_number.to_le_bytes().as_ref(),
+            &[free_deposit.pool_type],
+            free_deposit.consumed_round.to_le_bytes().as_ref(),
         ],
-        bump,
+        bump = draw_result.bump,
     )]
-    pub draw_result: Box<Account<'info, DrawResult>>,
-
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+    pub draw_result: Account<'info, DrawResult>,
 }
 
 #[derive(Accounts)]
-pub struct ExecuteRefund<'info> {
-    pub caller: Signer<'info>,
+pub struct CloseDrawResult<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
 
     #[account(
         mut,
-        seeds = [b"pool".as_ref(), &[pool_state.pool_type]],
-        bump = pool_state.bump,
+        seeds = [
+            b"draw_result".as_ref(),
+            &[draw_result.pool_type],
+            draw_result.round_number.to_le_bytes().as_ref(),
+        ],
+        bump = draw_result.bump,
+        constraint = draw_result.payer == payer.key() @ ErrorCode::Unauthorized,
+        close = payer,
     )]
-    pub pool_state: Account<'info, PoolState>,
+    pub draw_result: Account<'info, DrawResult>,
 
     #[account(
         mut,
-        constraint = pool_vault.key() == pool_state.vault @ ErrorCode::VaultMismatch,
+        seeds = [b"pool".as_ref(), &[draw_result.pool_type]],
+        bump = pool_state.bump,
     )]
-    pub pool_vault: Account<'info, TokenAccount>,
-
-    pub token_program: Program<'info, Token>,
+    pub pool_state: Account<'info, PoolState>,
 }
 
 #[derive(Accounts)]
@@ -1989,12 +5460,19 @@ pub struct ClaimFreeAirdrop<'info> {
     )]
     pub airdrop_claim: Account<'info, AirdropClaim>,
 
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct ClaimPrizeVesting<'info> {
     /// Anyone can call — permissionless (cron service calls daily)
+    #[account(mut)]
     pub caller: Signer<'info>,
 
     #[account(
@@ -2024,13 +5502,90 @@ pub struct ClaimPrizeVesting<'info> {
     #[account(mut)]
     pub winner_token_account: Account<'info, TokenAccount>,
 
+    /// CHECK: draw_result.top_winners[winner_index] — verified in instruction body
+    pub winner: UncheckedAccount<'info>,
+
+    /// Created by the caller if the winner has none yet
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = PLAYER_STATS_SIZE,
+        seeds = [b"player_stats", winner.key().as_ref()],
+        bump,
+    )]
+    pub player_stats: Box<Account<'info, PlayerStats>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(pool_type: u8, round_number: u64)]
+pub struct ClaimRoundPrize<'info> {
+    /// Anyone can call — prize always goes to the depositor's ATA
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool".as_ref(), &[pool_type]],
+        bump = pool_state.bump,
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        mut,
+        constraint = pool_vault.key() == pool_state.vault @ ErrorCode::VaultMismatch,
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"draw_result".as_ref(),
+            &[pool_type],
+            round_number.to_le_bytes().as_ref(),
+        ],
+        bump = draw_result.bump,
+    )]
+    pub draw_result: Account<'info, DrawResult>,
+
+    /// CHECK: participant's UserDeposit or FreeDeposit — verified as participant
+    /// `participant_index` by ParticipantSet::verify
+    pub deposit: UncheckedAccount<'info>,
+
+    /// Depositor's associated token account — verified in instruction body
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: the depositor recorded in `deposit` — verified in instruction body
+    pub user: UncheckedAccount<'info>,
+
+    /// Created by the caller if the depositor has none yet
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = PLAYER_STATS_SIZE,
+        seeds = [b"player_stats", user.key().as_ref()],
+        bump,
+    )]
+    pub player_stats: Box<Account<'info, PlayerStats>>,
+
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
 /// Context for claim_referral().
 /// Permissionless — cron (or anyone) calls this after a successful draw.
 #[derive(Accounts)]
 pub struct ClaimReferral<'info> {
+    #[account(mut)]
     pub caller: Signer<'info>,
 
     /// Proves the round was a successful draw (not a refund).
@@ -2048,6 +5603,48 @@ pub struct ClaimReferral<'info> {
     )]
     pub global_state: Account<'info, GlobalState>,
 
+    /// Must match user_deposit.referrer — verified in instruction body.
+    pub referrer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = REFERRAL_LEDGER_SIZE,
+        seeds = [b"referral_ledger", referrer_token_account.key().as_ref()],
+        bump,
+    )]
+    pub referral_ledger: Box<Account<'info, ReferralLedger>>,
+
+    #[account(mut, seeds = [b"vault_health"], bump = vault_health.bump)]
+    pub vault_health: Box<Account<'info, health::VaultHealth>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawReferralEarnings<'info> {
+    /// Owner of the referrer token account
+    pub referrer: Signer<'info>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [b"referral_ledger", referrer_token_account.key().as_ref()],
+        bump = referral_ledger.bump,
+    )]
+    pub referral_ledger: Box<Account<'info, ReferralLedger>>,
+
+    #[account(
+        mut,
+        constraint = referrer_token_account.owner == referrer.key() @ ErrorCode::UnauthorizedClaimer,
+    )]
+    pub referrer_token_account: Account<'info, TokenAccount>,
+
     /// Referral rewards vault. Authority = global_state PDA.
     #[account(
         mut,
@@ -2055,9 +5652,8 @@ pub struct ClaimReferral<'info> {
     )]
     pub referral_vault: Account<'info, TokenAccount>,
 
-    /// Must match user_deposit.referrer — verified in instruction body.
-    #[account(mut)]
-    pub referrer_token_account: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"vault_health"], bump = vault_health.bump)]
+    pub vault_health: Box<Account<'info, health::VaultHealth>>,
 
     pub token_program: Program<'info, Token>,
 }
@@ -2100,7 +5696,21 @@ pub struct ClaimRefereeBonus<'info> {
     )]
     pub user_token_account: Account<'info, TokenAccount>,
 
+    #[account(mut, seeds = [b"vault_health"], bump = vault_health.bump)]
+    pub vault_health: Box<Account<'info, health::VaultHealth>>,
+
+    /// Credited instead of paying when referral_vault is short
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + health::PayoutIou::SIZE,
+        seeds = [b"iou".as_ref(), &[VAULT_KIND_REFERRAL], user_token_account.key().as_ref()],
+        bump,
+    )]
+    pub iou: Box<Account<'info, health::PayoutIou>>,
+
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
 // ============================================================
@@ -2173,6 +5783,125 @@ pub struct ClaimVested<'info> {
 // Staking Accounts (Anchor requires #[derive(Accounts)] at crate root)
 // ============================================================
 
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = global_state.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + treasury::Treasury::SIZE,
+        seeds = [b"treasury"],
+        bump,
+    )]
+    pub treasury: Box<Account<'info, treasury::Treasury>>,
+    #[account(address = global_state.platform_fee_vault @ ErrorCode::PlatformVaultMismatch)]
+    pub platform_vault: Account<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RoutePlatformFees<'info> {
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Box<Account<'info, GlobalState>>,
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Box<Account<'info, treasury::Treasury>>,
+    #[account(mut, address = global_state.platform_fee_vault @ ErrorCode::PlatformVaultMismatch)]
+    pub platform_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = global_state.reserve_vault @ ErrorCode::ReserveVaultMismatch)]
+    pub reserve_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = global_state.referral_vault @ ErrorCode::ReferralVaultMismatch)]
+    pub referral_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = global_state.airdrop_vault @ ErrorCode::AirdropVaultMismatch)]
+    pub airdrop_vault: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct TreasuryWithdraw<'info> {
+    pub authority: Signer<'info>,
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Box<Account<'info, GlobalState>>,
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Box<Account<'info, treasury::Treasury>>,
+    #[account(mut, address = global_state.platform_fee_vault @ ErrorCode::PlatformVaultMismatch)]
+    pub platform_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = destination.mint == global_state.token_mint @ ErrorCode::MintMismatch,
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+    #[account(mut, seeds = [b"timelock_queue"], bump = timelock_queue.bump)]
+    pub timelock_queue: Box<Account<'info, TimelockQueue>>,
+    #[account(seeds = [b"admin_council"], bump = admin_council.bump)]
+    pub admin_council: Option<Box<Account<'info, AdminCouncil>>>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeVaultHealth<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = global_state.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + health::VaultHealth::SIZE,
+        seeds = [b"vault_health"],
+        bump,
+    )]
+    pub vault_health: Box<Account<'info, health::VaultHealth>>,
+    #[account(address = global_state.referral_vault @ ErrorCode::ReferralVaultMismatch)]
+    pub referral_vault: Box<Account<'info, TokenAccount>>,
+    #[account(address = global_state.airdrop_vault @ ErrorCode::AirdropVaultMismatch)]
+    pub airdrop_vault: Box<Account<'info, TokenAccount>>,
+    #[account(address = global_state.reserve_vault @ ErrorCode::ReserveVaultMismatch)]
+    pub reserve_vault: Box<Account<'info, TokenAccount>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CheckVaultHealth<'info> {
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Box<Account<'info, GlobalState>>,
+    #[account(mut, seeds = [b"vault_health"], bump = vault_health.bump)]
+    pub vault_health: Box<Account<'info, health::VaultHealth>>,
+    #[account(address = global_state.referral_vault @ ErrorCode::ReferralVaultMismatch)]
+    pub referral_vault: Box<Account<'info, TokenAccount>>,
+    #[account(address = global_state.airdrop_vault @ ErrorCode::AirdropVaultMismatch)]
+    pub airdrop_vault: Box<Account<'info, TokenAccount>>,
+    #[account(address = global_state.reserve_vault @ ErrorCode::ReserveVaultMismatch)]
+    pub reserve_vault: Box<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(kind: u8)]
+pub struct ClaimIou<'info> {
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Box<Account<'info, GlobalState>>,
+    #[account(mut, seeds = [b"vault_health"], bump = vault_health.bump)]
+    pub vault_health: Box<Account<'info, health::VaultHealth>>,
+    #[account(mut, seeds = [b"iou".as_ref(), &[kind], iou.beneficiary.as_ref()], bump = iou.bump)]
+    pub iou: Box<Account<'info, health::PayoutIou>>,
+    /// The vault of `kind` — checked in the handler
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = iou.beneficiary @ ErrorCode::VaultMismatch)]
+    pub beneficiary: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
 #[derive(Accounts)]
 pub struct InitializeStaking<'info> {
     #[account(mut)]
@@ -2196,6 +5925,8 @@ pub struct Stake<'info> {
     pub user: Signer<'info>,
     #[account(mut, seeds = [b"staking_state"], bump = staking_state.bump)]
     pub staking_state: Account<'info, staking::StakingState>,
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
     #[account(
         init,
         payer = user,
@@ -2219,6 +5950,8 @@ pub struct ReleaseStake<'info> {
     pub user: Signer<'info>,
     #[account(mut, seeds = [b"staking_state"], bump = staking_state.bump)]
     pub staking_state: Account<'info, staking::StakingState>,
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
     #[account(
         mut,
         seeds = [b"user_stake", user.key().as_ref(), &stake_index.to_le_bytes()],
@@ -2242,6 +5975,7 @@ pub struct ReleaseStake<'info> {
 
 #[derive(Accounts)]
 pub struct InitializeAirdrop<'info> {
+    /// GlobalState.authority or a council member (checked in the handler)
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(
@@ -2252,18 +5986,39 @@ pub struct InitializeAirdrop<'info> {
         bump,
     )]
     pub airdrop_state: Account<'info, airdrop::AirdropState>,
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Box<Account<'info, GlobalState>>,
+    #[account(address = global_state.token_mint @ ErrorCode::MintMismatch)]
     pub token_mint: Account<'info, Mint>,
+    /// VAULT_KIND_PROFIT_AIRDROP
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"vault".as_ref(), &[VAULT_KIND_PROFIT_AIRDROP]],
+        bump,
+        token::mint = token_mint,
+        token::authority = global_state,
+    )]
+    pub airdrop_vault: Box<Account<'info, TokenAccount>>,
+    #[account(seeds = [b"admin_council"], bump = admin_council.bump)]
+    pub admin_council: Option<Box<Account<'info, AdminCouncil>>>,
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct RecordProfit<'info> {
+    /// Anyone can call — the profit comes from the user's PlayerStats
     #[account(mut)]
     pub payer: Signer<'info>,
     /// CHECK: beneficiary whose profit is being recorded
     pub user: UncheckedAccount<'info>,
+    #[account(seeds = [b"player_stats", user.key().as_ref()], bump = player_stats.bump)]
+    pub player_stats: Box<Account<'info, PlayerStats>>,
     #[account(mut, seeds = [b"airdrop_state"], bump = airdrop_state.bump)]
     pub airdrop_state: Account<'info, airdrop::AirdropState>,
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
     #[account(
         init_if_needed,
         payer = payer,
@@ -2281,6 +6036,8 @@ pub struct ClaimProfitAirdrop<'info> {
     pub user: Signer<'info>,
     #[account(mut, seeds = [b"airdrop_state"], bump = airdrop_state.bump)]
     pub airdrop_state: Account<'info, airdrop::AirdropState>,
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
     #[account(
         mut,
         seeds = [b"user_airdrop", user.key().as_ref()],
@@ -2288,14 +6045,150 @@ pub struct ClaimProfitAirdrop<'info> {
         constraint = user_airdrop.owner == user.key(),
     )]
     pub user_airdrop: Account<'info, airdrop::UserAirdrop>,
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = user_token.owner == user.key() @ ErrorCode::PayoutAccountMismatch,
+        constraint = user_token.mint == airdrop_state.token_mint @ ErrorCode::MintMismatch,
+    )]
     pub user_token: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = airdrop_state.vault @ ErrorCode::AirdropVaultMismatch,
+        constraint = airdrop_vault.mint == airdrop_state.token_mint @ ErrorCode::MintMismatch,
+        constraint = airdrop_vault.owner == global_state.key() @ ErrorCode::AirdropVaultMismatch,
+    )]
+    pub airdrop_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct StartAirdropSeason<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"airdrop_state"],
+        bump = airdrop_state.bump,
+        constraint = airdrop_state.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub airdrop_state: Account<'info, airdrop::AirdropState>,
+    #[account(address = airdrop_state.vault @ ErrorCode::AirdropVaultMismatch)]
+    pub airdrop_vault: Account<'info, TokenAccount>,
+}
+
+#[derive(Accounts)]
+pub struct SweepUnclaimed<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"airdrop_state"],
+        bump = airdrop_state.bump,
+        constraint = airdrop_state.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub airdrop_state: Account<'info, airdrop::AirdropState>,
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(
+        mut,
+        address = airdrop_state.vault @ ErrorCode::AirdropVaultMismatch,
+        constraint = airdrop_vault.mint == airdrop_state.token_mint @ ErrorCode::MintMismatch,
+        constraint = airdrop_vault.owner == global_state.key() @ ErrorCode::AirdropVaultMismatch,
+    )]
+    pub airdrop_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = destination.mint == airdrop_state.token_mint @ ErrorCode::MintMismatch,
+    )]
+    pub destination: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(root: [u8; 32])]
+pub struct CreateDistribution<'info> {
     #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"airdrop_state"],
+        bump = airdrop_state.bump,
+        constraint = airdrop_state.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub airdrop_state: Account<'info, airdrop::AirdropState>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + airdrop::Distribution::SIZE,
+        seeds = [b"distribution".as_ref(), root.as_ref()],
+        bump,
+    )]
+    pub distribution: Account<'info, airdrop::Distribution>,
+    #[account(address = airdrop_state.vault @ ErrorCode::AirdropVaultMismatch)]
+    pub airdrop_vault: Account<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseDistribution<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"airdrop_state"],
+        bump = airdrop_state.bump,
+        constraint = airdrop_state.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub airdrop_state: Account<'info, airdrop::AirdropState>,
+    #[account(
+        mut,
+        seeds = [b"distribution".as_ref(), distribution.root.as_ref()],
+        bump = distribution.bump,
+        close = authority,
+    )]
+    pub distribution: Account<'info, airdrop::Distribution>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u32)]
+pub struct ClaimDistribution<'info> {
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+    #[account(mut, seeds = [b"airdrop_state"], bump = airdrop_state.bump)]
+    pub airdrop_state: Account<'info, airdrop::AirdropState>,
+    #[account(seeds = [b"global_state"], bump = global_state.bump)]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(
+        mut,
+        seeds = [b"distribution".as_ref(), distribution.root.as_ref()],
+        bump = distribution.bump,
+    )]
+    pub distribution: Account<'info, airdrop::Distribution>,
+    #[account(
+        init_if_needed,
+        payer = claimant,
+        space = 8 + airdrop::DistributionClaims::SIZE,
+        seeds = [
+            b"distribution_claims".as_ref(),
+            distribution.key().as_ref(),
+            &(index / airdrop::DISTRIBUTION_CHUNK_BITS).to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub distribution_claims: Box<Account<'info, airdrop::DistributionClaims>>,
+    #[account(
+        mut,
+        constraint = claimant_token.owner == claimant.key() @ ErrorCode::PayoutAccountMismatch,
+        constraint = claimant_token.mint == airdrop_state.token_mint @ ErrorCode::MintMismatch,
+    )]
+    pub claimant_token: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = airdrop_state.vault @ ErrorCode::AirdropVaultMismatch,
+        constraint = airdrop_vault.mint == airdrop_state.token_mint @ ErrorCode::MintMismatch,
+        constraint = airdrop_vault.owner == global_state.key() @ ErrorCode::AirdropVaultMismatch,
+    )]
     pub airdrop_vault: Account<'info, TokenAccount>,
-    /// CHECK: airdrop authority PDA
-    #[account(seeds = [b"airdrop"], bump)]
-    pub airdrop_authority: AccountInfo<'info>,
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
 // ============================================================
@@ -2307,6 +6200,13 @@ pub struct InitializeVrf<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = global_state.authority == authority.key() @ ErrorCode::Unauthorized,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
     #[account(
         init,
         payer = authority,
@@ -2323,6 +6223,7 @@ pub struct InitializeVrf<'info> {
 }
 
 #[derive(Accounts)]
+#[instruction(pool_type: u8)]
 pub struct RequestVrf<'info> {
     #[account(mut)]
     pub caller: Signer<'info>,
@@ -2335,12 +6236,22 @@ pub struct RequestVrf<'info> {
     pub vrf_state: Account<'info, VrfState>,
 
     #[account(
-        seeds = [b"pool".as_ref(), &[pool_state.pool_type]],
+        seeds = [b"pool".as_ref(), &[pool_type]],
         bump = pool_state.bump,
     )]
     pub pool_state: Account<'info, PoolState>,
 
-    /// CHECK: Switchboard VRF account
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = caller.key() == vrf_state.authority
+            || caller.key() == global_state.authority @ ErrorCode::Unauthorized,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    /// CHECK: randomness account — must be the one registered in vrf_state,
+    /// owner/layout verified by randomness::load_vrf_source
+    #[account(constraint = vrf.key() == vrf_state.vrf @ VrfErrorCode::VrfClientMismatch)]
     pub vrf: AccountInfo<'info>,
 
     pub system_program: Program<'info, System>,
@@ -2365,27 +6276,6 @@ pub struct ExecuteDrawVrf<'info> {
     )]
     pub pool_state: Box<Account<'info, PoolState>>,
 
-    #[account(
-        mut,
-        constraint = pool_vault.key() == pool_state.vault @ ErrorCode::VaultMismatch,
-    )]
-    pub pool_vault: Box<Account<'info, TokenAccount>>,
-
-    #[account(mut)]
-    pub token_mint: Account<'info, Mint>,
-
-    #[account(
-        mut,
-        constraint = platform_vault.key() == global_state.platform_fee_vault @ ErrorCode::PlatformVaultMismatch,
-    )]
-    pub platform_vault: Box<Account<'info, TokenAccount>>,
-
-    #[account(
-        mut,
-        constraint = prize_escrow_vault.key() == global_state.prize_escrow_vault @ ErrorCode::PrizeEscrowMismatch,
-    )]
-    pub prize_escrow_vault: Box<Account<'info, TokenAccount>>,
-
     #[account(
         seeds = [b"global_state"],
         bump = global_state.bump,
@@ -2395,7 +6285,7 @@ pub struct ExecuteDrawVrf<'info> {
     #[account(
         init,
         payer = caller,
-        space = DRAW_RESULT_SIZE,
+        space = draw_result_size(pool_state.regular_count + pool_state.free_count),
         seeds = [
             b"draw_result".as_ref(),
             &[pool_state.pool_type],
@@ -2405,9 +6295,24 @@ pub struct ExecuteDrawVrf<'info> {
     )]
     pub draw_result: Box<Account<'info, DrawResult>>,
 
-    /// CHECK: Switchboard VRF account
+    /// DrawProgress PDA — drives crank_draw until the round is finalized.
+    #[account(
+        init,
+        payer = caller,
+        space = DRAW_PROGRESS_SIZE,
+        seeds = [
+            b"draw_progress".as_ref(),
+            &[pool_state.pool_type],
+            pool_state.round_number.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub draw_progress: Box<Account<'info, DrawProgress>>,
+
+    /// CHECK: randomness account — must be the one registered in vrf_state,
+    /// owner/layout verified by randomness::load_vrf_source
+    #[account(constraint = vrf.key() == vrf_state.vrf @ VrfErrorCode::VrfClientMismatch)]
     pub vrf: AccountInfo<'info>,
 
-    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
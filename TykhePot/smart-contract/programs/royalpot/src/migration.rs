@@ -0,0 +1,730 @@
+use crate::airdrop::{AirdropState, AIRDROP_STATE_VERSION};
+use crate::{
+    ErrorCode, GlobalState, PoolState, RoundParams, GLOBAL_STATE_SIZE, PAUSE_ALL, POOL_STATE_SIZE,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+
+// ============================================================
+// Account versions
+// ============================================================
+//
+// GlobalState, PoolState and AirdropState start with a `version` byte. Layouts only ever
+// grow, so an account still on an older layout is shorter than the current
+// struct and fails to deserialize until migrate_* upgrades it. Layouts from
+// before the header are recognised by their exact size.
+
+pub const GLOBAL_STATE_VERSION: u8 = 2;
+pub const POOL_STATE_VERSION: u8 = 3;
+
+/// GlobalState v0 — launch layout
+pub const GLOBAL_STATE_V0_SIZE: usize = 256;
+/// GlobalState v1 — pause_flags + guardian
+pub const GLOBAL_STATE_V1_SIZE: usize = 288;
+/// PoolState v0 — launch layout
+pub const POOL_STATE_V0_SIZE: usize = 105;
+/// PoolState v1 — + draw_in_progress, reserve_matched, pending_payouts
+pub const POOL_STATE_V1_SIZE: usize = 121;
+/// PoolState v2 — + params, open_draw_results
+pub const POOL_STATE_V2_SIZE: usize = 213;
+/// AirdropState v0 — before seasons
+pub const AIRDROP_STATE_V0_SIZE: usize = 105;
+/// AirdropState v1 — + seasons
+pub const AIRDROP_STATE_V1_SIZE: usize = 143;
+
+// ------------------------------------------------------------
+// Historical layouts (without discriminator)
+// ------------------------------------------------------------
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct GlobalStateV0 {
+    pub token_mint: Pubkey,
+    pub platform_fee_vault: Pubkey,
+    pub airdrop_vault: Pubkey,
+    pub referral_vault: Pubkey,
+    pub reserve_vault: Pubkey,
+    pub prize_escrow_vault: Pubkey,
+    pub authority: Pubkey,
+    pub is_paused: bool,
+    pub timelock_duration: i64,
+    pub pending_operation: u8,
+    pub timelock_release: i64,
+    pub bump: u8,
+    pub _padding: [u8; 5],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct GlobalStateV1 {
+    pub token_mint: Pubkey,
+    pub platform_fee_vault: Pubkey,
+    pub airdrop_vault: Pubkey,
+    pub referral_vault: Pubkey,
+    pub reserve_vault: Pubkey,
+    pub prize_escrow_vault: Pubkey,
+    pub authority: Pubkey,
+    pub pause_flags: u8,
+    pub timelock_duration: i64,
+    pub _reserved: [u8; 9],
+    pub bump: u8,
+    pub _padding: [u8; 5],
+    pub guardian: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PoolStateV0 {
+    pub pool_type: u8,
+    pub round_number: u64,
+    pub round_start_time: i64,
+    pub round_end_time: i64,
+    pub total_deposited: u64,
+    pub free_bet_total: u64,
+    pub regular_count: u32,
+    pub free_count: u32,
+    pub vault: Pubkey,
+    pub rollover: u64,
+    pub bump: u8,
+    pub _padding: [u8; 7],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PoolStateV1 {
+    pub pool_type: u8,
+    pub round_number: u64,
+    pub round_start_time: i64,
+    pub round_end_time: i64,
+    pub total_deposited: u64,
+    pub free_bet_total: u64,
+    pub regular_count: u32,
+    pub free_count: u32,
+    pub vault: Pubkey,
+    pub rollover: u64,
+    pub bump: u8,
+    pub draw_in_progress: bool,
+    pub _padding: [u8; 6],
+    pub reserve_matched: u64,
+    pub pending_payouts: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PoolStateV2 {
+    pub pool_type: u8,
+    pub round_number: u64,
+    pub round_start_time: i64,
+    pub round_end_time: i64,
+    pub total_deposited: u64,
+    pub free_bet_total: u64,
+    pub regular_count: u32,
+    pub free_count: u32,
+    pub vault: Pubkey,
+    pub rollover: u64,
+    pub bump: u8,
+    pub draw_in_progress: bool,
+    pub open_draw_results: u32,
+    pub _padding: [u8; 2],
+    pub reserve_matched: u64,
+    pub pending_payouts: u64,
+    pub params: RoundParams,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AirdropStateV0 {
+    pub authority: Pubkey,
+    pub token_mint: Pubkey,
+    pub total_airdrop: u64,
+    pub claimed_amount: u64,
+    pub remaining_amount: u64,
+    pub participant_count: u32,
+    pub claimed_count: u32,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AirdropStateV1 {
+    pub version: u8,
+    pub authority: Pubkey,
+    pub token_mint: Pubkey,
+    pub total_airdrop: u64,
+    pub claimed_amount: u64,
+    pub remaining_amount: u64,
+    pub participant_count: u32,
+    pub claimed_count: u32,
+    pub bump: u8,
+    pub season: u32,
+    pub season_budget: u64,
+    pub season_claimed: u64,
+    pub season_start: i64,
+    pub season_end: i64,
+    pub season_swept: bool,
+}
+
+// ------------------------------------------------------------
+// Upgrades
+// ------------------------------------------------------------
+
+/// Parse GlobalState data of any historical layout into the current one.
+/// Returns the version it was read from.
+pub fn read_global_state(data: &[u8]) -> Result<(u8, GlobalState)> {
+    let body = strip_discriminator(data, &GlobalState::DISCRIMINATOR)?;
+    match data.len() {
+        GLOBAL_STATE_V0_SIZE => {
+            let v0 = GlobalStateV0::deserialize(&mut &body[..])?;
+            Ok((
+                0,
+                GlobalState {
+                    version: GLOBAL_STATE_VERSION,
+                    token_mint: v0.token_mint,
+                    platform_fee_vault: v0.platform_fee_vault,
+                    airdrop_vault: v0.airdrop_vault,
+                    referral_vault: v0.referral_vault,
+                    reserve_vault: v0.reserve_vault,
+                    prize_escrow_vault: v0.prize_escrow_vault,
+                    authority: v0.authority,
+                    pause_flags: if v0.is_paused { PAUSE_ALL } else { 0 },
+                    timelock_duration: v0.timelock_duration,
+                    // The single-slot pending operation is dropped; reschedule
+                    // it through the TimelockQueue.
+                    _reserved: [0u8; 9],
+                    bump: v0.bump,
+                    _padding: [0u8; 5],
+                    guardian: Pubkey::default(),
+                },
+            ))
+        }
+        GLOBAL_STATE_V1_SIZE => {
+            let v1 = GlobalStateV1::deserialize(&mut &body[..])?;
+            Ok((
+                1,
+                GlobalState {
+                    version: GLOBAL_STATE_VERSION,
+                    token_mint: v1.token_mint,
+                    platform_fee_vault: v1.platform_fee_vault,
+                    airdrop_vault: v1.airdrop_vault,
+                    referral_vault: v1.referral_vault,
+                    reserve_vault: v1.reserve_vault,
+                    prize_escrow_vault: v1.prize_escrow_vault,
+                    authority: v1.authority,
+                    pause_flags: v1.pause_flags,
+                    timelock_duration: v1.timelock_duration,
+                    _reserved: [0u8; 9],
+                    bump: v1.bump,
+                    _padding: [0u8; 5],
+                    guardian: v1.guardian,
+                },
+            ))
+        }
+        GLOBAL_STATE_SIZE if body[0] == GLOBAL_STATE_VERSION => err!(ErrorCode::AlreadyMigrated),
+        _ => err!(ErrorCode::UnknownAccountLayout),
+    }
+}
+
+/// Parse PoolState data of any historical layout into the current one.
+/// `params` supplies the round parameters layouts before v2 did not store.
+pub fn read_pool_state(data: &[u8], params: RoundParams) -> Result<(u8, PoolState)> {
+    let body = strip_discriminator(data, &PoolState::DISCRIMINATOR)?;
+    match data.len() {
+        POOL_STATE_V0_SIZE => {
+            let v0 = PoolStateV0::deserialize(&mut &body[..])?;
+            Ok((
+                0,
+                PoolState {
+                    version: POOL_STATE_VERSION,
+                    pool_type: v0.pool_type,
+                    round_number: v0.round_number,
+                    round_start_time: v0.round_start_time,
+                    round_end_time: v0.round_end_time,
+                    total_deposited: v0.total_deposited,
+                    free_bet_total: v0.free_bet_total,
+                    regular_count: v0.regular_count,
+                    free_count: v0.free_count,
+                    vault: v0.vault,
+                    rollover: v0.rollover,
+                    bump: v0.bump,
+                    draw_in_progress: false,
+                    open_draw_results: 0,
+                    _padding: [0u8; 2],
+                    reserve_matched: 0,
+                    pending_payouts: 0,
+                    params,
+                },
+            ))
+        }
+        POOL_STATE_V1_SIZE => {
+            let v1 = PoolStateV1::deserialize(&mut &body[..])?;
+            Ok((
+                1,
+                PoolState {
+                    version: POOL_STATE_VERSION,
+                    pool_type: v1.pool_type,
+                    round_number: v1.round_number,
+                    round_start_time: v1.round_start_time,
+                    round_end_time: v1.round_end_time,
+                    total_deposited: v1.total_deposited,
+                    free_bet_total: v1.free_bet_total,
+                    regular_count: v1.regular_count,
+                    free_count: v1.free_count,
+                    vault: v1.vault,
+                    rollover: v1.rollover,
+                    bump: v1.bump,
+                    draw_in_progress: v1.draw_in_progress,
+                    open_draw_results: 0,
+                    _padding: [0u8; 2],
+                    reserve_matched: v1.reserve_matched,
+                    pending_payouts: v1.pending_payouts,
+                    params,
+                },
+            ))
+        }
+        POOL_STATE_V2_SIZE => {
+            let v2 = PoolStateV2::deserialize(&mut &body[..])?;
+            Ok((
+                2,
+                PoolState {
+                    version: POOL_STATE_VERSION,
+                    pool_type: v2.pool_type,
+                    round_number: v2.round_number,
+                    round_start_time: v2.round_start_time,
+                    round_end_time: v2.round_end_time,
+                    total_deposited: v2.total_deposited,
+                    free_bet_total: v2.free_bet_total,
+                    regular_count: v2.regular_count,
+                    free_count: v2.free_count,
+                    vault: v2.vault,
+                    rollover: v2.rollover,
+                    bump: v2.bump,
+                    draw_in_progress: v2.draw_in_progress,
+                    open_draw_results: v2.open_draw_results,
+                    _padding: [0u8; 2],
+                    reserve_matched: v2.reserve_matched,
+                    pending_payouts: v2.pending_payouts,
+                    params: v2.params,
+                },
+            ))
+        }
+        POOL_STATE_SIZE if body[0] == POOL_STATE_VERSION => err!(ErrorCode::AlreadyMigrated),
+        _ => err!(ErrorCode::UnknownAccountLayout),
+    }
+}
+
+/// Parse AirdropState data of any historical layout into the current one.
+/// A v0 state has no season yet; what it had left becomes unallocated budget.
+/// Layouts before v2 have no registered vault: `vault` is left default for
+/// migrate_airdrop_state to fill in.
+pub fn read_airdrop_state(data: &[u8]) -> Result<(u8, AirdropState)> {
+    let body = strip_discriminator(data, &AirdropState::DISCRIMINATOR)?;
+    match data.len() {
+        AIRDROP_STATE_V0_SIZE => {
+            let v0 = AirdropStateV0::deserialize(&mut &body[..])?;
+            Ok((
+                0,
+                AirdropState {
+                    version: AIRDROP_STATE_VERSION,
+                    authority: v0.authority,
+                    token_mint: v0.token_mint,
+                    total_airdrop: v0.total_airdrop,
+                    claimed_amount: v0.claimed_amount,
+                    remaining_amount: v0.remaining_amount,
+                    participant_count: v0.participant_count,
+                    claimed_count: v0.claimed_count,
+                    bump: v0.bump,
+                    season: 0,
+                    season_budget: 0,
+                    season_claimed: 0,
+                    season_start: 0,
+                    season_end: 0,
+                    season_swept: false,
+                    vault: Pubkey::default(),
+                },
+            ))
+        }
+        AIRDROP_STATE_V1_SIZE => {
+            let v1 = AirdropStateV1::deserialize(&mut &body[..])?;
+            Ok((
+                1,
+                AirdropState {
+                    version: AIRDROP_STATE_VERSION,
+                    authority: v1.authority,
+                    token_mint: v1.token_mint,
+                    total_airdrop: v1.total_airdrop,
+                    claimed_amount: v1.claimed_amount,
+                    remaining_amount: v1.remaining_amount,
+                    participant_count: v1.participant_count,
+                    claimed_count: v1.claimed_count,
+                    bump: v1.bump,
+                    season: v1.season,
+                    season_budget: v1.season_budget,
+                    season_claimed: v1.season_claimed,
+                    season_start: v1.season_start,
+                    season_end: v1.season_end,
+                    season_swept: v1.season_swept,
+                    vault: Pubkey::default(),
+                },
+            ))
+        }
+        n if n == 8 + AirdropState::SIZE && body[0] == AIRDROP_STATE_VERSION => {
+            err!(ErrorCode::AlreadyMigrated)
+        }
+        _ => err!(ErrorCode::UnknownAccountLayout),
+    }
+}
+
+fn strip_discriminator<'a>(data: &'a [u8], discriminator: &[u8; 8]) -> Result<&'a [u8]> {
+    require!(
+        data.len() > 8 && data[..8] == discriminator[..],
+        ErrorCode::UnknownAccountLayout
+    );
+    Ok(&data[8..])
+}
+
+/// Resize `info` to `new_len`, topping up rent from `payer`, and write
+/// `value` (with discriminator) over it.
+pub fn store_resized<'info, T: AccountSerialize>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    value: &T,
+    new_len: usize,
+) -> Result<()> {
+    let rent_due = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(info.lamports());
+    if rent_due > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    info.realloc(new_len, true)?;
+    let mut data = info.try_borrow_mut_data()?;
+    value.try_serialize(&mut &mut data[..])
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ProtocolParams, PAUSE_DEPOSITS};
+
+    fn with_discriminator<T: AnchorSerialize>(disc: [u8; 8], body: &T) -> Vec<u8> {
+        let mut data = disc.to_vec();
+        body.serialize(&mut data).unwrap();
+        data
+    }
+
+    fn key(n: u8) -> Pubkey {
+        Pubkey::new_from_array([n; 32])
+    }
+
+    fn params() -> RoundParams {
+        ProtocolParams::default().for_pool(2).unwrap()
+    }
+
+    #[test]
+    fn global_state_v0_upgrades() {
+        let v0 = GlobalStateV0 {
+            token_mint: key(1),
+            platform_fee_vault: key(2),
+            airdrop_vault: key(3),
+            referral_vault: key(4),
+            reserve_vault: key(5),
+            prize_escrow_vault: key(6),
+            authority: key(7),
+            is_paused: true,
+            timelock_duration: 86_400,
+            pending_operation: 2,
+            timelock_release: 1_700_000_000,
+            bump: 254,
+            _padding: [0u8; 5],
+        };
+        let data = with_discriminator(GlobalState::DISCRIMINATOR, &v0);
+        assert_eq!(data.len(), GLOBAL_STATE_V0_SIZE);
+
+        let (from, state) = read_global_state(&data).unwrap();
+        assert_eq!(from, 0);
+        assert_eq!(state.version, GLOBAL_STATE_VERSION);
+        assert_eq!(state.token_mint, key(1));
+        assert_eq!(state.prize_escrow_vault, key(6));
+        assert_eq!(state.authority, key(7));
+        assert_eq!(state.pause_flags, PAUSE_ALL);
+        assert_eq!(state.timelock_duration, 86_400);
+        assert_eq!(state._reserved, [0u8; 9]);
+        assert_eq!(state.bump, 254);
+        assert_eq!(state.guardian, Pubkey::default());
+    }
+
+    #[test]
+    fn global_state_v1_upgrades() {
+        let v1 = GlobalStateV1 {
+            token_mint: key(1),
+            platform_fee_vault: key(2),
+            airdrop_vault: key(3),
+            referral_vault: key(4),
+            reserve_vault: key(5),
+            prize_escrow_vault: key(6),
+            authority: key(7),
+            pause_flags: PAUSE_DEPOSITS,
+            timelock_duration: 3_600,
+            _reserved: [0u8; 9],
+            bump: 253,
+            _padding: [0u8; 5],
+            guardian: key(8),
+        };
+        let data = with_discriminator(GlobalState::DISCRIMINATOR, &v1);
+        assert_eq!(data.len(), GLOBAL_STATE_V1_SIZE);
+
+        let (from, state) = read_global_state(&data).unwrap();
+        assert_eq!(from, 1);
+        assert_eq!(state.version, GLOBAL_STATE_VERSION);
+        assert_eq!(state.authority, key(7));
+        assert_eq!(state.pause_flags, PAUSE_DEPOSITS);
+        assert_eq!(state.timelock_duration, 3_600);
+        assert_eq!(state.bump, 253);
+        assert_eq!(state.guardian, key(8));
+    }
+
+    #[test]
+    fn global_state_current_round_trips_and_is_not_remigrated() {
+        let data = with_discriminator(
+            GlobalState::DISCRIMINATOR,
+            &GlobalStateV0 {
+                token_mint: key(1),
+                platform_fee_vault: key(2),
+                airdrop_vault: key(3),
+                referral_vault: key(4),
+                reserve_vault: key(5),
+                prize_escrow_vault: key(6),
+                authority: key(7),
+                is_paused: false,
+                timelock_duration: 60,
+                pending_operation: 0,
+                timelock_release: 0,
+                bump: 1,
+                _padding: [0u8; 5],
+            },
+        );
+        let (_, state) = read_global_state(&data).unwrap();
+
+        let mut current = Vec::new();
+        state.try_serialize(&mut current).unwrap();
+        assert_eq!(current.len(), GLOBAL_STATE_SIZE);
+        let reread = GlobalState::try_deserialize(&mut &current[..]).unwrap();
+        assert_eq!(reread.authority, key(7));
+        assert_eq!(reread.pause_flags, 0);
+
+        assert!(read_global_state(&current).is_err());
+    }
+
+    #[test]
+    fn old_global_state_does_not_load_as_current() {
+        for len in [GLOBAL_STATE_V0_SIZE, GLOBAL_STATE_V1_SIZE] {
+            assert!(len < GLOBAL_STATE_SIZE);
+            let mut data = GlobalState::DISCRIMINATOR.to_vec();
+            data.resize(len, 0);
+            assert!(GlobalState::try_deserialize(&mut &data[..]).is_err());
+        }
+    }
+
+    #[test]
+    fn pool_state_v0_upgrades() {
+        let v0 = PoolStateV0 {
+            pool_type: 2,
+            round_number: 41,
+            round_start_time: 1_000,
+            round_end_time: 87_400,
+            total_deposited: 500,
+            free_bet_total: 100,
+            regular_count: 5,
+            free_count: 1,
+            vault: key(9),
+            rollover: 77,
+            bump: 252,
+            _padding: [0u8; 7],
+        };
+        let data = with_discriminator(PoolState::DISCRIMINATOR, &v0);
+        assert_eq!(data.len(), POOL_STATE_V0_SIZE);
+
+        let (from, pool) = read_pool_state(&data, params()).unwrap();
+        assert_eq!(from, 0);
+        assert_eq!(pool.version, POOL_STATE_VERSION);
+        assert_eq!(pool.pool_type, 2);
+        assert_eq!(pool.round_number, 41);
+        assert_eq!(pool.round_end_time, 87_400);
+        assert_eq!(pool.total_deposited, 500);
+        assert_eq!(pool.free_bet_total, 100);
+        assert_eq!(pool.regular_count, 5);
+        assert_eq!(pool.free_count, 1);
+        assert_eq!(pool.vault, key(9));
+        assert_eq!(pool.rollover, 77);
+        assert_eq!(pool.bump, 252);
+        assert!(!pool.draw_in_progress);
+        assert_eq!(pool.reserve_matched, 0);
+        assert_eq!(pool.pending_payouts, 0);
+        assert_eq!(pool.params, params());
+    }
+
+    #[test]
+    fn pool_state_v1_upgrades() {
+        let v1 = PoolStateV1 {
+            pool_type: 1,
+            round_number: 7,
+            round_start_time: 0,
+            round_end_time: 3_600,
+            total_deposited: 900,
+            free_bet_total: 0,
+            regular_count: 3,
+            free_count: 0,
+            vault: key(9),
+            rollover: 5,
+            bump: 251,
+            draw_in_progress: true,
+            _padding: [0u8; 6],
+            reserve_matched: 300,
+            pending_payouts: 40,
+        };
+        let data = with_discriminator(PoolState::DISCRIMINATOR, &v1);
+        assert_eq!(data.len(), POOL_STATE_V1_SIZE);
+
+        let (from, pool) = read_pool_state(&data, params()).unwrap();
+        assert_eq!(from, 1);
+        assert_eq!(pool.round_number, 7);
+        assert!(pool.draw_in_progress);
+        assert_eq!(pool.open_draw_results, 0);
+        assert_eq!(pool.reserve_matched, 300);
+        assert_eq!(pool.pending_payouts, 40);
+        assert_eq!(pool.params, params());
+    }
+
+    #[test]
+    fn pool_state_v2_keeps_its_params() {
+        let mut own = params();
+        own.duration = 1_234;
+        let v2 = PoolStateV2 {
+            pool_type: 0,
+            round_number: 3,
+            round_start_time: 0,
+            round_end_time: 1_234,
+            total_deposited: 0,
+            free_bet_total: 0,
+            regular_count: 0,
+            free_count: 0,
+            vault: key(9),
+            rollover: 0,
+            bump: 250,
+            draw_in_progress: false,
+            open_draw_results: 2,
+            _padding: [0u8; 2],
+            reserve_matched: 0,
+            pending_payouts: 10,
+            params: own,
+        };
+        let data = with_discriminator(PoolState::DISCRIMINATOR, &v2);
+        assert_eq!(data.len(), POOL_STATE_V2_SIZE);
+
+        let (from, pool) = read_pool_state(&data, params()).unwrap();
+        assert_eq!(from, 2);
+        assert_eq!(pool.open_draw_results, 2);
+        assert_eq!(pool.pending_payouts, 10);
+        assert_eq!(pool.params, own);
+
+        let mut current = Vec::new();
+        pool.try_serialize(&mut current).unwrap();
+        assert_eq!(current.len(), POOL_STATE_SIZE);
+        assert!(PoolState::try_deserialize(&mut &current[..]).is_ok());
+        assert!(read_pool_state(&current, params()).is_err());
+    }
+
+    #[test]
+    fn old_pool_state_does_not_load_as_current() {
+        for len in [POOL_STATE_V0_SIZE, POOL_STATE_V1_SIZE, POOL_STATE_V2_SIZE] {
+            assert!(len < POOL_STATE_SIZE);
+            let mut data = PoolState::DISCRIMINATOR.to_vec();
+            data.resize(len, 0);
+            assert!(PoolState::try_deserialize(&mut &data[..]).is_err());
+        }
+    }
+
+    #[test]
+    fn unknown_layouts_are_rejected() {
+        let mut data = GlobalState::DISCRIMINATOR.to_vec();
+        data.resize(GLOBAL_STATE_V0_SIZE + 1, 0);
+        assert!(read_global_state(&data).is_err());
+
+        let mut data = PoolState::DISCRIMINATOR.to_vec();
+        data.resize(POOL_STATE_V0_SIZE, 0);
+        assert!(read_global_state(&data).is_err());
+    }
+
+    #[test]
+    fn airdrop_state_v0_upgrades_without_a_season() {
+        let v0 = AirdropStateV0 {
+            authority: key(1),
+            token_mint: key(2),
+            total_airdrop: 1_000,
+            claimed_amount: 300,
+            remaining_amount: 700,
+            participant_count: 9,
+            claimed_count: 4,
+            bump: 251,
+        };
+        let data = with_discriminator(AirdropState::DISCRIMINATOR, &v0);
+        assert_eq!(data.len(), AIRDROP_STATE_V0_SIZE);
+
+        let (from, state) = read_airdrop_state(&data).unwrap();
+        assert_eq!(from, 0);
+        assert_eq!(state.version, AIRDROP_STATE_VERSION);
+        assert_eq!(state.authority, key(1));
+        assert_eq!(state.remaining_amount, 700);
+        assert_eq!(state.claimed_count, 4);
+        assert_eq!(state.season, 0);
+        assert!(!state.season_open(0));
+        assert_eq!(state.vault, Pubkey::default());
+
+        let mut current = Vec::new();
+        state.try_serialize(&mut current).unwrap();
+        assert_eq!(current.len(), 8 + AirdropState::SIZE);
+        assert!(read_airdrop_state(&current).is_err());
+    }
+
+    #[test]
+    fn airdrop_state_v1_keeps_its_season() {
+        let v1 = AirdropStateV1 {
+            version: 1,
+            authority: key(1),
+            token_mint: key(2),
+            total_airdrop: 1_000,
+            claimed_amount: 100,
+            remaining_amount: 400,
+            participant_count: 3,
+            claimed_count: 1,
+            bump: 250,
+            season: 2,
+            season_budget: 500,
+            season_claimed: 100,
+            season_start: 10,
+            season_end: 20,
+            season_swept: false,
+        };
+        let data = with_discriminator(AirdropState::DISCRIMINATOR, &v1);
+        assert_eq!(data.len(), AIRDROP_STATE_V1_SIZE);
+
+        let (from, state) = read_airdrop_state(&data).unwrap();
+        assert_eq!(from, 1);
+        assert_eq!(state.version, AIRDROP_STATE_VERSION);
+        assert_eq!(state.season, 2);
+        assert_eq!(state.season_unclaimed(), 400);
+        assert!(state.season_open(15));
+        assert_eq!(state.vault, Pubkey::default());
+    }
+}
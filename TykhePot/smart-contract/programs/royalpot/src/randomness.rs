@@ -1,6 +1,6 @@
-use crate::PoolType;
+use crate::{PoolType, MAX_VRF_REQUESTS, VRF_TIMEOUT_SECONDS};
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::hash::{hash, hashv};
 
 pub const VRF_IR_RETRIES: u8 = 5;
 
@@ -42,10 +42,15 @@ pub enum PrizeType {
 
 #[account]
 pub struct VrfState {
+    /// Cron key allowed to request_vrf (besides GlobalState.authority)
     pub authority: Pubkey,
     pub vrf: Pubkey,
     pub pending_draw: Option<PendingDraw>,
     pub bump: u8,
+    /// Round of the latest request and how many requests it has had
+    pub request_pool: u8,
+    pub request_round: u64,
+    pub request_count: u8,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -57,94 +62,322 @@ pub struct PendingDraw {
 }
 
 impl VrfState {
-    pub const SIZE: usize = 32 + 32 + (1 + 1 + 8 + 8 + 8) + 1 + 64;
+    // request_* came out of the original 64 bytes of padding
+    pub const SIZE: usize = 32 + 32 + (1 + 1 + 8 + 8 + 8) + 1 + (1 + 8 + 1) + 54;
+
+    /// Requests already made for round `round_number` of `pool_type`.
+    pub fn requests_for(&self, pool_type: u8, round_number: u64) -> u8 {
+        if self.request_pool == pool_type && self.request_round == round_number {
+            self.request_count
+        } else {
+            0
+        }
+    }
+
+    /// The round used up its MAX_VRF_REQUESTS and the last one timed out —
+    /// it can no longer be drawn by VRF and goes to execute_refund.
+    pub fn requests_exhausted(&self, pool_type: u8, round_number: u64, now: i64) -> bool {
+        self.requests_for(pool_type, round_number) >= MAX_VRF_REQUESTS
+            && self.pending_draw.as_ref().is_some_and(|p| {
+                p.pool_type == pool_type
+                    && p.round_number == round_number
+                    && now - p.request_timestamp > VRF_TIMEOUT_SECONDS
+            })
+    }
 }
 
 impl PendingDraw {
     pub const SIZE: usize = 1 + 8 + 8 + 8;
 }
 
-pub fn pick_winner_indices_vrf(randomness: [u8; 32], total: usize) -> Result<[usize; 11]> {
-    require!(total >= 11, VrfErrorCode::InsufficientParticipants);
+// ============================================================
+// Randomness sources
+// ============================================================
+
+/// A source of draw randomness whose value is fixed (committed) before anyone can know it.
+///
+/// request_vrf binds a round to `commit_slot()` while `is_revealed()` is still false;
+/// execute_draw_vrf later takes `reveal()` from the same commitment.
+pub trait RandomnessSource {
+    /// Slot the randomness was committed at.
+    fn commit_slot(&self) -> u64;
+    /// Whether the value for the current commitment is already public.
+    fn is_revealed(&self) -> bool;
+    /// The 32-byte value. Errors if it is not (validly) revealed yet.
+    fn reveal(&self, clock: &Clock) -> Result<[u8; 32]>;
+}
 
-    let mut pool: Vec<usize> = (0..total).collect();
-    let mut seed = randomness;
+/// Randomness read from an oracle-owned account (Switchboard or the local mock oracle).
+/// seed_slot   = slot the randomness was committed at (fixed before the value exists)
+/// reveal_slot = slot the oracle revealed `value` (0 / stale until revealed)
+pub struct OracleRandomness {
+    pub seed_slot: u64,
+    pub reveal_slot: u64,
+    pub value: [u8; 32],
+}
 
-    for i in 0..11 {
-        seed = hash(&seed).to_bytes();
-        let random_u64 = u64::from_le_bytes([
-            seed[0], seed[1], seed[2], seed[3], seed[4], seed[5], seed[6], seed[7],
-        ]);
-        let j = i + (random_u64 as usize) % (total - i);
-        pool.swap(i, j);
+impl RandomnessSource for OracleRandomness {
+    fn commit_slot(&self) -> u64 {
+        self.seed_slot
     }
 
-    let mut result = [0usize; 11];
-    result.copy_from_slice(&pool[0..11]);
-    Ok(result)
-}
+    fn is_revealed(&self) -> bool {
+        self.reveal_slot >= self.seed_slot
+    }
 
-pub fn generate_winning_numbers(
-    vrf_randomness: [u8; 32],
-    total_tickets: u64,
-) -> Result<WinningNumbers> {
-    require!(total_tickets > 0, VrfErrorCode::InvalidTicketCount);
-    require!(total_tickets >= 11, VrfErrorCode::InsufficientTickets);
+    /// Only accepted in the reveal slot itself, so the reveal and the draw share a
+    /// transaction and nobody can see the value first and decide not to draw.
+    fn reveal(&self, clock: &Clock) -> Result<[u8; 32]> {
+        require!(
+            self.is_revealed() && self.reveal_slot == clock.slot,
+            VrfErrorCode::RandomnessNotReady
+        );
+        Ok(self.value)
+    }
+}
 
-    let mut numbers = WinningNumbers {
-        first_prize: 0,
-        second_prizes: Vec::new(),
-        third_prizes: Vec::new(),
-        lucky_prizes: Vec::new(),
-    };
+/// Commit-reveal randomness: the committer stores hash(seed) first, then reveals `seed`,
+/// which is mixed with the SlotHashes entry of a slot fixed at commit time but only
+/// produced after deposits close.
+pub struct CommitRevealRandomness {
+    pub commitment: [u8; 32],
+    pub commit_slot: u64,
+    pub seed: Option<[u8; 32]>,
+    pub slot_hash: [u8; 32],
+}
 
-    let mut seed = vrf_randomness;
+impl RandomnessSource for CommitRevealRandomness {
+    fn commit_slot(&self) -> u64 {
+        self.commit_slot
+    }
 
-    numbers.first_prize = generate_unique_number(&mut seed, total_tickets, &[])?;
+    fn is_revealed(&self) -> bool {
+        self.seed.is_some()
+    }
 
-    let mut used_numbers = vec![numbers.first_prize];
-    for _ in 0..2 {
-        let num = generate_unique_number(&mut seed, total_tickets, &used_numbers)?;
-        numbers.second_prizes.push(num);
-        used_numbers.push(num);
+    fn reveal(&self, _clock: &Clock) -> Result<[u8; 32]> {
+        let seed = self.seed.ok_or(VrfErrorCode::RandomnessNotReady)?;
+        require!(
+            hash(&seed).to_bytes() == self.commitment,
+            VrfErrorCode::CommitmentMismatch
+        );
+        Ok(hashv(&[&seed, &self.slot_hash]).to_bytes())
     }
+}
 
-    for _ in 0..3 {
-        let num = generate_unique_number(&mut seed, total_tickets, &used_numbers)?;
-        numbers.third_prizes.push(num);
-        used_numbers.push(num);
+/// Load the randomness source behind the account registered in VrfState.
+/// Switchboard On-Demand in production; the local mock oracle with `--features mock-oracle`.
+pub fn load_vrf_source(acc: &AccountInfo) -> Result<Box<dyn RandomnessSource>> {
+    #[cfg(feature = "mock-oracle")]
+    if *acc.owner == MOCK_ORACLE_PROGRAM_ID {
+        return Ok(Box::new(read_mock_randomness(acc)?));
     }
+    Ok(Box::new(read_switchboard_randomness(acc)?))
+}
 
-    for _ in 0..5 {
-        let num = generate_unique_number(&mut seed, total_tickets, &used_numbers)?;
-        numbers.lucky_prizes.push(num);
-        used_numbers.push(num);
+/// Hash of the first produced slot at or after `slot`, from the SlotHashes sysvar.
+///
+/// Fails unless an older entry is still listed too — only then is the match known
+/// to be the first block at or after `slot` (skipped slots have no entry). So the
+/// hash is unavailable until `slot` has passed and again once it is ~512 slots old.
+pub fn slot_hash_at(acc: &AccountInfo, slot: u64) -> Result<[u8; 32]> {
+    require!(
+        *acc.key == anchor_lang::solana_program::sysvar::slot_hashes::ID,
+        VrfErrorCode::SlotHashUnavailable
+    );
+    let data = acc.try_borrow_data()?;
+    find_slot_hash(&data, slot).ok_or_else(|| VrfErrorCode::SlotHashUnavailable.into())
+}
+
+/// Layout: len(u64) then `len` × (slot u64, hash [u8; 32]), newest first.
+fn find_slot_hash(data: &[u8], slot: u64) -> Option<[u8; 32]> {
+    let len = u64::from_le_bytes(data.get(0..8)?.try_into().ok()?) as usize;
+    let mut found = None;
+    for i in 0..len {
+        let entry = data.get(8 + i * 40..8 + (i + 1) * 40)?;
+        if u64::from_le_bytes(entry[0..8].try_into().ok()?) < slot {
+            return found;
+        }
+        found = Some(entry[8..40].try_into().ok()?);
     }
+    None
+}
 
-    Ok(numbers)
+// ------------------------------------------------------------
+// Switchboard On-Demand randomness account (read without the SDK)
+// ------------------------------------------------------------
+
+pub const SWITCHBOARD_ON_DEMAND_MAINNET: Pubkey =
+    pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+pub const SWITCHBOARD_ON_DEMAND_DEVNET: Pubkey =
+    pubkey!("Aio4gaXjXzJNVLtzwtNVmSqGKpANtXhybbkhtAC94ji2");
+
+pub const SB_RANDOMNESS_DISCRIMINATOR: [u8; 8] = [10, 66, 229, 135, 220, 239, 217, 114];
+// RandomnessAccountData: disc(8)+authority(32)+queue(32)+seed_slothash(32)+seed_slot(8)+
+//                        oracle(32)+reveal_slot(8)+value(32)+ebuf(224) = 408
+pub const SB_RANDOMNESS_SIZE: usize = 8 + 32 + 32 + 32 + 8 + 32 + 8 + 32 + 224;
+const SB_SEED_SLOT_OFFSET: usize = 8 + 32 + 32 + 32;
+const SB_REVEAL_SLOT_OFFSET: usize = SB_SEED_SLOT_OFFSET + 8 + 32;
+const SB_VALUE_OFFSET: usize = SB_REVEAL_SLOT_OFFSET + 8;
+
+pub fn read_switchboard_randomness(acc: &AccountInfo) -> Result<OracleRandomness> {
+    require!(
+        *acc.owner == SWITCHBOARD_ON_DEMAND_MAINNET || *acc.owner == SWITCHBOARD_ON_DEMAND_DEVNET,
+        VrfErrorCode::VrfClientMismatch
+    );
+    let data = acc.try_borrow_data()?;
+    require!(
+        data.len() >= SB_RANDOMNESS_SIZE && data[0..8] == SB_RANDOMNESS_DISCRIMINATOR,
+        VrfErrorCode::VrfClientMismatch
+    );
+    Ok(read_oracle_fields(
+        &data,
+        SB_SEED_SLOT_OFFSET,
+        SB_REVEAL_SLOT_OFFSET,
+        SB_VALUE_OFFSET,
+    ))
 }
 
-fn generate_unique_number(seed: &mut [u8; 32], max: u64, used: &[u64]) -> Result<u64> {
-    let mut attempts = 0;
-    const MAX_ATTEMPTS: u32 = 1000;
+// ------------------------------------------------------------
+// Local mock oracle (programs/mock-oracle) — test validators only
+// ------------------------------------------------------------
+
+#[cfg(feature = "mock-oracle")]
+pub const MOCK_ORACLE_PROGRAM_ID: Pubkey = pubkey!("H8cBoQsrXbWgSAyL19EoPvFPNJZ5XLqhdbtfZ8CM7sxU");
+
+// MockRandomness: disc(8)+authority(32)+seed_slot(8)+reveal_slot(8)+value(32) = 88
+#[cfg(feature = "mock-oracle")]
+const MOCK_RANDOMNESS_DISCRIMINATOR: [u8; 8] = [178, 216, 41, 24, 156, 153, 7, 104];
+#[cfg(feature = "mock-oracle")]
+const MOCK_RANDOMNESS_SIZE: usize = 8 + 32 + 8 + 8 + 32;
+
+#[cfg(feature = "mock-oracle")]
+pub fn read_mock_randomness(acc: &AccountInfo) -> Result<OracleRandomness> {
+    require!(
+        *acc.owner == MOCK_ORACLE_PROGRAM_ID,
+        VrfErrorCode::VrfClientMismatch
+    );
+    let data = acc.try_borrow_data()?;
+    require!(
+        data.len() >= MOCK_RANDOMNESS_SIZE && data[0..8] == MOCK_RANDOMNESS_DISCRIMINATOR,
+        VrfErrorCode::VrfClientMismatch
+    );
+    Ok(read_oracle_fields(&data, 40, 48, 56))
+}
+
+fn read_oracle_fields(
+    data: &[u8],
+    seed_slot_off: usize,
+    reveal_slot_off: usize,
+    value_off: usize,
+) -> OracleRandomness {
+    let read_u64 = |off: usize| u64::from_le_bytes(data[off..off + 8].try_into().unwrap());
+    let mut value = [0u8; 32];
+    value.copy_from_slice(&data[value_off..value_off + 32]);
+    OracleRandomness {
+        seed_slot: read_u64(seed_slot_off),
+        reveal_slot: read_u64(reveal_slot_off),
+        value,
+    }
+}
 
-    loop {
-        require!(attempts < MAX_ATTEMPTS, VrfErrorCode::TooManyAttempts);
-        attempts += 1;
+// ============================================================
+// Winner selection — shared by every draw path
+// ============================================================
 
-        *seed = hash(seed).to_bytes();
+/// Prize slots per draw: [1st, 2nd_a, 2nd_b, 3rd_a, 3rd_b, 3rd_c, lucky×5]
+pub const WINNER_COUNT: usize = 11;
+
+/// sha256 hash chain: the k-th output is sha256(seed || k as u64 LE), read as a
+/// little-endian u64 from the first 8 bytes.
+struct HashChain {
+    seed: [u8; 32],
+    counter: u64,
+}
+
+impl HashChain {
+    fn new(seed: [u8; 32]) -> Self {
+        Self { seed, counter: 0 }
+    }
 
-        let number = (u64::from_le_bytes([
-            seed[0], seed[1], seed[2], seed[3], seed[4], seed[5], seed[6], seed[7],
-        ]) % max)
-            + 1;
+    fn next_u64(&mut self) -> u64 {
+        let h = hashv(&[&self.seed, &self.counter.to_le_bytes()]).to_bytes();
+        self.counter += 1;
+        u64::from_le_bytes(h[0..8].try_into().unwrap())
+    }
 
-        if !used.contains(&number) {
-            return Ok(number);
+    /// Uniform value in [0, bound). Outputs in the top partial bucket
+    /// (≥ u64::MAX - u64::MAX % bound) are rejected, so there is no modulo bias.
+    fn below(&mut self, bound: u64) -> u64 {
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return x % bound;
+            }
         }
     }
 }
 
+/// Pick WINNER_COUNT distinct participant indices in [0, total) from `draw_seed`.
+///
+/// Pure and deterministic: feeding `DrawExecuted.draw_seed` and
+/// `DrawExecuted.participant_count` reproduces the on-chain winner set.
+/// Algorithm (partial Fisher–Yates over 0..total):
+///   for i in 0..11: j = i + below(total - i); swap(i, j); winner[i] = slot[i]
+/// where below() is the rejection-sampled HashChain above. Only the displaced
+/// positions are stored, so memory does not grow with `total`.
+pub fn pick_winners(draw_seed: [u8; 32], total: usize) -> Result<[usize; WINNER_COUNT]> {
+    require!(
+        total >= WINNER_COUNT,
+        VrfErrorCode::InsufficientParticipants
+    );
+
+    let mut rng = HashChain::new(draw_seed);
+    // (position, value) overrides; the latest entry for a position wins
+    let mut moved: Vec<(usize, usize)> = Vec::with_capacity(2 * WINNER_COUNT);
+    let value_at = |moved: &[(usize, usize)], pos: usize| {
+        moved
+            .iter()
+            .rev()
+            .find(|(p, _)| *p == pos)
+            .map_or(pos, |(_, v)| *v)
+    };
+
+    let mut winners = [0usize; WINNER_COUNT];
+    for (i, winner) in winners.iter_mut().enumerate() {
+        let j = i + rng.below((total - i) as u64) as usize;
+        let vi = value_at(&moved, i);
+        let vj = value_at(&moved, j);
+        moved.push((j, vi));
+        moved.push((i, vj));
+        *winner = vj;
+    }
+    Ok(winners)
+}
+
+/// Ticket-number view of pick_winners (tickets are numbered 1..=total_tickets).
+pub fn generate_winning_numbers(
+    vrf_randomness: [u8; 32],
+    total_tickets: u64,
+) -> Result<WinningNumbers> {
+    require!(total_tickets > 0, VrfErrorCode::InvalidTicketCount);
+    require!(
+        total_tickets >= WINNER_COUNT as u64,
+        VrfErrorCode::InsufficientTickets
+    );
+
+    let idx = pick_winners(vrf_randomness, total_tickets as usize)?;
+    let ticket = |i: usize| idx[i] as u64 + 1;
+
+    Ok(WinningNumbers {
+        first_prize: ticket(0),
+        second_prizes: (1..3).map(ticket).collect(),
+        third_prizes: (3..6).map(ticket).collect(),
+        lucky_prizes: (6..11).map(ticket).collect(),
+    })
+}
+
 pub fn check_winner(
     user_tickets: (u64, u64),
     winning_numbers: &WinningNumbers,
@@ -222,4 +455,84 @@ pub enum VrfErrorCode {
     NoPendingVrfRequest,
     #[msg("VRF request timeout")]
     VrfRequestTimeout,
+    #[msg("VRF randomness was not committed in the previous slot")]
+    StaleRandomness,
+    #[msg("VRF request does not match this pool round")]
+    PendingDrawMismatch,
+    #[msg("Revealed seed does not match the stored commitment")]
+    CommitmentMismatch,
+    #[msg("Entropy slot hash is not in SlotHashes — not reached yet or too old")]
+    SlotHashUnavailable,
+    #[msg("Round used up its VRF requests — use execute_refund")]
+    VrfRequestsExhausted,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// SlotHashes data for `slots` (newest first), hash = [slot as u8; 32].
+    fn slot_hashes(slots: &[u64]) -> Vec<u8> {
+        let mut data = (slots.len() as u64).to_le_bytes().to_vec();
+        for &slot in slots {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&[slot as u8; 32]);
+        }
+        data
+    }
+
+    fn assert_distinct_below(winners: &[usize], total: usize) {
+        let mut sorted = winners.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(sorted.len(), WINNER_COUNT);
+        assert!(sorted.iter().all(|&w| w < total));
+    }
+
+    #[test]
+    fn pick_winners_returns_distinct_indices_below_total() {
+        for (n, total) in [12usize, 13, 50, 1_000, 1_000_000].into_iter().enumerate() {
+            let winners = pick_winners([n as u8; 32], total).unwrap();
+            assert_distinct_below(&winners, total);
+        }
+    }
+
+    // app/src/sdk.ts pickWinners must reproduce this vector
+    #[test]
+    fn pick_winners_is_deterministic_for_a_fixed_seed() {
+        let winners = pick_winners([7; 32], 100).unwrap();
+        assert_eq!(winners, [92, 77, 28, 71, 22, 36, 97, 63, 99, 21, 29]);
+        assert_eq!(pick_winners([7; 32], 100).unwrap(), winners);
+        assert_ne!(pick_winners([8; 32], 100).unwrap(), winners);
+    }
+
+    #[test]
+    fn pick_winners_permutes_when_everyone_wins() {
+        let winners = pick_winners([7; 32], WINNER_COUNT).unwrap();
+        assert_eq!(winners, [8, 1, 10, 5, 9, 2, 3, 0, 7, 4, 6]);
+        assert_distinct_below(&winners, WINNER_COUNT);
+    }
+
+    #[test]
+    fn pick_winners_needs_winner_count_participants() {
+        assert!(pick_winners([7; 32], WINNER_COUNT - 1).is_err());
+        assert!(pick_winners([7; 32], 0).is_err());
+    }
+
+    #[test]
+    fn slot_hash_is_first_block_at_or_after_slot() {
+        let data = slot_hashes(&[105, 104, 102, 101]);
+        assert_eq!(find_slot_hash(&data, 102), Some([102; 32]));
+        // 103 was skipped — the next produced block stands in for it
+        assert_eq!(find_slot_hash(&data, 103), Some([104; 32]));
+    }
+
+    #[test]
+    fn slot_hash_unavailable_before_slot_or_after_it_ages_out() {
+        let data = slot_hashes(&[105, 104, 102, 101]);
+        assert_eq!(find_slot_hash(&data, 106), None);
+        // Oldest listed entry: cannot tell whether an earlier block matched
+        assert_eq!(find_slot_hash(&data, 101), None);
+        assert_eq!(find_slot_hash(&data, 90), None);
+    }
 }
@@ -97,6 +97,12 @@ pub fn stake(
     amount: u64,
     stake_type: StakeType,
 ) -> Result<()> {
+    // 检查暂停状态
+    require!(
+        !ctx.accounts.global_state.paused(crate::PAUSE_STAKING),
+        crate::ErrorCode::ProtocolPaused
+    );
+
     require!(amount > 0, StakingErrorCode::InvalidAmount);
 
     let staking_state = &mut ctx.accounts.staking_state;
@@ -173,6 +179,12 @@ pub fn stake(
 
 // 到期释放质押
 pub fn release_stake(ctx: Context<crate::ReleaseStake>, _stake_index: u64) -> Result<()> {
+    // 检查暂停状态
+    require!(
+        !ctx.accounts.global_state.paused(crate::PAUSE_STAKING),
+        crate::ErrorCode::ProtocolPaused
+    );
+
     let staking_state = &mut ctx.accounts.staking_state;
     let user_stake = &mut ctx.accounts.user_stake;
     let clock = Clock::get()?;
@@ -228,6 +240,12 @@ pub fn release_stake(ctx: Context<crate::ReleaseStake>, _stake_index: u64) -> Re
 
 // 提前赎回（无收益）
 pub fn early_withdraw(ctx: Context<crate::ReleaseStake>, _stake_index: u64) -> Result<()> {
+    // 检查暂停状态
+    require!(
+        !ctx.accounts.global_state.paused(crate::PAUSE_STAKING),
+        crate::ErrorCode::ProtocolPaused
+    );
+
     let staking_state = &mut ctx.accounts.staking_state;
     let user_stake = &mut ctx.accounts.user_stake;
     let clock = Clock::get()?;
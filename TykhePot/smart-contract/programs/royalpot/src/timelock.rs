@@ -0,0 +1,237 @@
+use crate::{
+    ErrorCode, MAX_COUNCIL_MEMBERS, MAX_TIMELOCK_OPS, TIMELOCK_GRACE_PERIOD, TIMELOCK_OP_NONE,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+// ============================================================
+// Timelock queue
+// ============================================================
+
+/// Admin operations waiting out the timelock. Each slot is one scheduled
+/// operation; `op_type == TIMELOCK_OP_NONE` marks a free slot.
+#[account]
+pub struct TimelockQueue {
+    /// Id handed to the next scheduled operation
+    pub next_id: u64,
+    pub ops: [TimelockOp; MAX_TIMELOCK_OPS],
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug)]
+pub struct TimelockOp {
+    pub id: u64,
+    pub op_type: u8,
+    /// payload_hash(op_type, payload) — execute_op must present the same payload
+    pub payload_hash: [u8; 32],
+    /// Earliest execution time
+    pub eta: i64,
+    /// Last execution time (eta + TIMELOCK_GRACE_PERIOD); afterwards only cancel_op
+    pub expires_at: i64,
+    /// Bit i set = AdminCouncil.members[i] approved (unused without a council)
+    pub approvals: u16,
+}
+
+impl TimelockOp {
+    pub const SIZE: usize = 8 + 1 + 32 + 8 + 8 + 2;
+}
+
+impl TimelockQueue {
+    pub const SIZE: usize = 8 + TimelockOp::SIZE * MAX_TIMELOCK_OPS + 1;
+
+    /// Queue an operation in the first free slot and return it.
+    pub fn schedule(
+        &mut self,
+        op_type: u8,
+        payload: &[u8],
+        eta: i64,
+        approvals: u16,
+    ) -> Result<TimelockOp> {
+        let slot = self
+            .ops
+            .iter_mut()
+            .find(|op| op.op_type == TIMELOCK_OP_NONE)
+            .ok_or(ErrorCode::TimelockQueueFull)?;
+        *slot = TimelockOp {
+            id: self.next_id,
+            op_type,
+            payload_hash: payload_hash(op_type, payload),
+            eta,
+            expires_at: eta
+                .checked_add(TIMELOCK_GRACE_PERIOD)
+                .ok_or(ErrorCode::MathOverflow)?,
+            approvals,
+        };
+        self.next_id = self.next_id.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        Ok(*slot)
+    }
+
+    /// Remove operation `id` if `payload` matches and `now` is inside its window.
+    pub fn take_ready(&mut self, id: u64, payload: &[u8], now: i64) -> Result<TimelockOp> {
+        let slot = self.find_mut(id)?;
+        require!(
+            slot.payload_hash == payload_hash(slot.op_type, payload),
+            ErrorCode::PayloadHashMismatch
+        );
+        require!(now >= slot.eta, ErrorCode::TimelockNotExpired);
+        require!(now <= slot.expires_at, ErrorCode::TimelockOpExpired);
+        Ok(std::mem::take(slot))
+    }
+
+    /// Record council member `index`'s approval of operation `id`.
+    pub fn approve(&mut self, id: u64, index: u8) -> Result<TimelockOp> {
+        let slot = self.find_mut(id)?;
+        let bit = 1u16 << index;
+        require!(slot.approvals & bit == 0, ErrorCode::AlreadyApproved);
+        slot.approvals |= bit;
+        Ok(*slot)
+    }
+
+    /// Approvals are council-member positions; they mean nothing once the
+    /// membership changes.
+    pub fn clear_approvals(&mut self) {
+        for op in self.ops.iter_mut() {
+            op.approvals = 0;
+        }
+    }
+
+    /// Remove operation `id` regardless of its window.
+    pub fn take(&mut self, id: u64) -> Result<TimelockOp> {
+        Ok(std::mem::take(self.find_mut(id)?))
+    }
+
+    fn find_mut(&mut self, id: u64) -> Result<&mut TimelockOp> {
+        Ok(self
+            .ops
+            .iter_mut()
+            .find(|op| op.op_type != TIMELOCK_OP_NONE && op.id == id)
+            .ok_or(ErrorCode::TimelockOpNotFound)?)
+    }
+}
+
+// ============================================================
+// Admin council
+// ============================================================
+
+/// M-of-N admin. When GlobalState.authority is this PDA's address, any member
+/// may schedule/approve/cancel queued operations, and execute_op requires
+/// `threshold` distinct approvals.
+#[account]
+pub struct AdminCouncil {
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    pub bump: u8,
+}
+
+impl AdminCouncil {
+    pub const SIZE: usize = 4 + 32 * MAX_COUNCIL_MEMBERS + 1 + 1;
+
+    pub fn member_index(&self, key: &Pubkey) -> Option<u8> {
+        self.members.iter().position(|m| m == key).map(|i| i as u8)
+    }
+
+    pub fn approved(&self, approvals: u16) -> bool {
+        approvals.count_ones() >= self.threshold as u32
+    }
+}
+
+/// Commits an operation to both its type and its exact payload bytes.
+pub fn payload_hash(op_type: u8, payload: &[u8]) -> [u8; 32] {
+    hashv(&[&[op_type], payload]).to_bytes()
+}
+
+// ------------------------------------------------------------
+// Payloads (borsh-encoded into schedule_op / execute_op)
+// ------------------------------------------------------------
+
+/// TIMELOCK_OP_VAULT: move one GlobalState vault to a fresh PDA
+/// [b"vault", &[kind], &nonce.to_le_bytes()] (see rotate_vault).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct VaultRotation {
+    /// VAULT_KIND_* constant
+    pub kind: u8,
+    /// Picks the new vault address; must not have been used for this kind
+    pub nonce: u64,
+}
+
+/// TIMELOCK_OP_AUTHORITY: hand a council-held `target` (GlobalState,
+/// StakingState or AirdropState) to `new_authority` (see propose_authority).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct AuthorityHandover {
+    pub target: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+/// TIMELOCK_OP_CLOSE_POOL: close one PoolState, rent to `recipient`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ClosePool {
+    pub pool_type: u8,
+    pub recipient: Pubkey,
+}
+
+/// TIMELOCK_OP_COUNCIL (and initialize_admin_council): council membership.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CouncilConfig {
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+impl CouncilConfig {
+    /// 1 ≤ threshold ≤ members ≤ MAX_COUNCIL_MEMBERS, members distinct and non-default.
+    pub fn validate(&self) -> Result<()> {
+        let n = self.members.len();
+        require!(
+            n <= MAX_COUNCIL_MEMBERS && self.threshold >= 1 && (self.threshold as usize) <= n,
+            ErrorCode::InvalidCouncil
+        );
+        let mut sorted = self.members.clone();
+        sorted.sort();
+        sorted.dedup();
+        require!(
+            sorted.len() == n && !sorted.contains(&Pubkey::default()),
+            ErrorCode::InvalidCouncil
+        );
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------
+// Events
+// ------------------------------------------------------------
+
+#[event]
+pub struct TimelockOpScheduled {
+    pub id: u64,
+    pub op_type: u8,
+    pub payload: Vec<u8>,
+    pub payload_hash: [u8; 32],
+    pub eta: i64,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct TimelockOpApproved {
+    pub id: u64,
+    pub member: Pubkey,
+    pub approvals: u16,
+}
+
+#[event]
+pub struct TimelockOpExecuted {
+    pub id: u64,
+    pub op_type: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TimelockOpCancelled {
+    pub id: u64,
+    pub op_type: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminCouncilUpdated {
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
@@ -0,0 +1,302 @@
+use crate::{
+    take_queued_op, ErrorCode, BASE, MAX_TREASURY_DESTINATIONS, PAUSE_TREASURY,
+    TIMELOCK_OP_TREASURY_WITHDRAW, VAULT_KIND_AIRDROP, VAULT_KIND_REFERRAL, VAULT_KIND_RESERVE,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+
+// ============================================================
+// Treasury
+// ============================================================
+//
+// Rules for tokens leaving platform_fee_vault. Fees first pass through
+// route_platform_fees, which may top up the reserve / referral / airdrop
+// vaults; what is left becomes `withdrawable` and can only leave through a
+// timelocked TIMELOCK_OP_TREASURY_WITHDRAW to a whitelisted destination,
+// capped at `epoch_limit` per `epoch_duration`.
+
+/// Routing slots, in TreasuryConfig.route_bp / route_target order.
+pub const ROUTE_KINDS: [u8; 3] = [VAULT_KIND_RESERVE, VAULT_KIND_REFERRAL, VAULT_KIND_AIRDROP];
+
+#[account]
+pub struct Treasury {
+    pub config: TreasuryConfig,
+    /// Start of the current rate-limit window
+    pub epoch_start: i64,
+    /// Withdrawn since epoch_start
+    pub epoch_withdrawn: u64,
+    /// Part of platform_fee_vault already routed and free to withdraw;
+    /// anything above it is fee income route_platform_fees has not seen yet
+    pub withdrawable: u64,
+    pub total_routed: u64,
+    pub total_withdrawn: u64,
+    pub bump: u8,
+}
+
+impl Treasury {
+    pub const SIZE: usize = TreasuryConfig::SIZE + 8 + 8 + 8 + 8 + 8 + 1;
+}
+
+/// TIMELOCK_OP_TREASURY_CONFIG (and initialize_treasury).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TreasuryConfig {
+    /// Token accounts withdrawals may be sent to
+    pub destinations: Vec<Pubkey>,
+    pub epoch_duration: i64,
+    /// Max withdrawn per epoch
+    pub epoch_limit: u64,
+    /// Share of new fee income routed to reserve / referral / airdrop vault (bp)
+    pub route_bp: [u16; 3],
+    /// Route only while the vault holds less than this; 0 = no cap
+    pub route_target: [u64; 3],
+}
+
+impl TreasuryConfig {
+    pub const SIZE: usize = 4 + 32 * MAX_TREASURY_DESTINATIONS + 8 + 8 + 2 * 3 + 8 * 3;
+
+    /// ≤ MAX_TREASURY_DESTINATIONS distinct non-default destinations,
+    /// epoch_duration > 0, routing shares ≤ 100%.
+    pub fn validate(&self) -> Result<()> {
+        let n = self.destinations.len();
+        let mut sorted = self.destinations.clone();
+        sorted.sort();
+        sorted.dedup();
+        require!(
+            n <= MAX_TREASURY_DESTINATIONS
+                && sorted.len() == n
+                && !sorted.contains(&Pubkey::default()),
+            ErrorCode::InvalidTreasuryConfig
+        );
+        require!(self.epoch_duration > 0, ErrorCode::InvalidTreasuryConfig);
+        let route_total: u64 = self.route_bp.iter().map(|&bp| bp as u64).sum();
+        require!(route_total <= BASE, ErrorCode::InvalidTreasuryConfig);
+        Ok(())
+    }
+}
+
+/// TIMELOCK_OP_TREASURY_WITHDRAW: send `amount` from platform_fee_vault to
+/// whitelisted `destination`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct TreasuryWithdrawal {
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+// ------------------------------------------------------------
+// Handlers
+// ------------------------------------------------------------
+
+pub fn initialize_treasury(
+    ctx: Context<crate::InitializeTreasury>,
+    config: TreasuryConfig,
+) -> Result<()> {
+    config.validate()?;
+    let clock = Clock::get()?;
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.config = config.clone();
+    treasury.epoch_start = clock.unix_timestamp;
+    treasury.epoch_withdrawn = 0;
+    // Fees collected before the treasury existed skip routing
+    treasury.withdrawable = ctx.accounts.platform_vault.amount;
+    treasury.total_routed = 0;
+    treasury.total_withdrawn = 0;
+    treasury.bump = ctx.bumps.treasury;
+
+    emit!(TreasuryConfigUpdated {
+        config,
+        timestamp: clock.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Permissionless crank: split fee income that arrived since the last call
+/// between the routed vaults, then mark the rest withdrawable.
+pub fn route_platform_fees(ctx: Context<crate::RoutePlatformFees>) -> Result<()> {
+    require!(
+        !ctx.accounts.global_state.paused(PAUSE_TREASURY),
+        ErrorCode::ProtocolPaused
+    );
+    let clock = Clock::get()?;
+    let balance = ctx.accounts.platform_vault.amount;
+    let treasury = &mut ctx.accounts.treasury;
+    // A rotation away from a legacy platform vault can leave less than was
+    // accounted for
+    let withdrawable = treasury.withdrawable.min(balance);
+    let income = balance - withdrawable;
+
+    let gs_bump = ctx.accounts.global_state.bump;
+    let gs_seeds: &[&[u8]] = &[b"global_state", &[gs_bump]];
+    let signer = &[gs_seeds];
+    let targets = [
+        &ctx.accounts.reserve_vault,
+        &ctx.accounts.referral_vault,
+        &ctx.accounts.airdrop_vault,
+    ];
+
+    let mut routed: u64 = 0;
+    for (i, vault) in targets.into_iter().enumerate() {
+        let mut share = (income as u128)
+            .checked_mul(treasury.config.route_bp[i] as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(BASE as u128)
+            .ok_or(ErrorCode::MathOverflow)? as u64;
+        let target = treasury.config.route_target[i];
+        if target > 0 {
+            share = share.min(target.saturating_sub(vault.amount));
+        }
+        if share == 0 {
+            continue;
+        }
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.platform_vault.to_account_info(),
+                    to: vault.to_account_info(),
+                    authority: ctx.accounts.global_state.to_account_info(),
+                },
+                signer,
+            ),
+            share,
+        )?;
+        routed = routed.checked_add(share).ok_or(ErrorCode::MathOverflow)?;
+        emit!(TreasuryRouted {
+            kind: ROUTE_KINDS[i],
+            vault: vault.key(),
+            amount: share,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    treasury.withdrawable = balance.checked_sub(routed).ok_or(ErrorCode::MathOverflow)?;
+    treasury.total_routed = treasury
+        .total_routed
+        .checked_add(routed)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(())
+}
+
+/// Run a ready TIMELOCK_OP_TREASURY_WITHDRAW operation. The destination must
+/// still be whitelisted and the amount must fit the current epoch's limit.
+pub fn treasury_withdraw(
+    ctx: Context<crate::TreasuryWithdraw>,
+    op_id: u64,
+    withdrawal: TreasuryWithdrawal,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.global_state.paused(PAUSE_TREASURY),
+        ErrorCode::ProtocolPaused
+    );
+    let clock = Clock::get()?;
+    let op = take_queued_op(
+        &mut ctx.accounts.timelock_queue,
+        &ctx.accounts.global_state,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.admin_council.as_deref(),
+        op_id,
+        &withdrawal.try_to_vec()?,
+        clock.unix_timestamp,
+    )?;
+    require!(
+        op.op_type == TIMELOCK_OP_TREASURY_WITHDRAW,
+        ErrorCode::OperationMismatch
+    );
+
+    let treasury = &mut ctx.accounts.treasury;
+    require!(
+        ctx.accounts.destination.key() == withdrawal.destination
+            && treasury
+                .config
+                .destinations
+                .contains(&withdrawal.destination),
+        ErrorCode::DestinationNotWhitelisted
+    );
+
+    if clock.unix_timestamp
+        >= treasury
+            .epoch_start
+            .checked_add(treasury.config.epoch_duration)
+            .ok_or(ErrorCode::MathOverflow)?
+    {
+        treasury.epoch_start = clock.unix_timestamp;
+        treasury.epoch_withdrawn = 0;
+    }
+    let epoch_withdrawn = treasury
+        .epoch_withdrawn
+        .checked_add(withdrawal.amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(
+        epoch_withdrawn <= treasury.config.epoch_limit,
+        ErrorCode::TreasuryRateLimited
+    );
+    let withdrawable = treasury
+        .withdrawable
+        .min(ctx.accounts.platform_vault.amount);
+    require!(
+        withdrawal.amount <= withdrawable,
+        ErrorCode::InsufficientTreasuryBalance
+    );
+
+    let gs_bump = ctx.accounts.global_state.bump;
+    let gs_seeds: &[&[u8]] = &[b"global_state", &[gs_bump]];
+    let signer = &[gs_seeds];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.platform_vault.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.global_state.to_account_info(),
+            },
+            signer,
+        ),
+        withdrawal.amount,
+    )?;
+
+    treasury.epoch_withdrawn = epoch_withdrawn;
+    treasury.withdrawable = withdrawable - withdrawal.amount;
+    treasury.total_withdrawn = treasury
+        .total_withdrawn
+        .checked_add(withdrawal.amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(TreasuryWithdrawn {
+        destination: withdrawal.destination,
+        amount: withdrawal.amount,
+        epoch_withdrawn,
+        timestamp: clock.unix_timestamp,
+    });
+    emit!(crate::timelock::TimelockOpExecuted {
+        id: op_id,
+        op_type: op.op_type,
+        timestamp: clock.unix_timestamp,
+    });
+    Ok(())
+}
+
+// ------------------------------------------------------------
+// Events
+// ------------------------------------------------------------
+
+#[event]
+pub struct TreasuryConfigUpdated {
+    pub config: TreasuryConfig,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryRouted {
+    /// VAULT_KIND_* of the receiving vault
+    pub kind: u8,
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub epoch_withdrawn: u64,
+    pub timestamp: i64,
+}